@@ -180,3 +180,38 @@ pub struct PipelineApproverAction {
 }
 register_class!("org.jenkinsci.plugins.workflow.support.steps.input.ApproverAction" => PipelineApproverAction);
 impl Action for PipelineApproverAction {}
+
+/// Metadata of an SCM object, like the title and URL of a pull request
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectMetadataAction {
+    /// Description of the object
+    pub object_description: Option<String>,
+    /// Display name of the object, like the title of a pull request
+    pub object_display_name: Option<String>,
+    /// URL of the object
+    pub object_url: Option<String>,
+}
+register_class!("jenkins.scm.api.metadata.ObjectMetadataAction" => ObjectMetadataAction);
+impl Action for ObjectMetadataAction {}
+
+/// Metadata of the contributor of an SCM object, like the author of a pull request
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributorMetadataAction {
+    /// ID of the contributor
+    pub contributor: Option<String>,
+    /// Display name of the contributor
+    pub contributor_display_name: Option<String>,
+    /// Email of the contributor
+    pub contributor_email: Option<String>,
+}
+register_class!("jenkins.scm.api.metadata.ContributorMetadataAction" => ContributorMetadataAction);
+impl Action for ContributorMetadataAction {}
+
+/// Marks the primary SCM object, like the default branch
+#[derive(Deserialize, Debug, Copy, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrimaryInstanceMetadataAction {}
+register_class!("jenkins.scm.api.metadata.PrimaryInstanceMetadataAction" => PrimaryInstanceMetadataAction);
+impl Action for PrimaryInstanceMetadataAction {}
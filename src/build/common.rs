@@ -263,6 +263,69 @@ pub trait Build {
         }
         .into())
     }
+
+    /// Set the description of a `Build`
+    fn set_description(&self, jenkins_client: &Jenkins, description: &str) -> Result<()> {
+        let _ = jenkins_client.post_with_body(
+            &build_page(jenkins_client, self.url(), "submitDescription")?,
+            serde_urlencoded::to_string([("description", description)])?,
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Set the display name of a `Build`, or reset it to `#N` with `None`, keeping its
+    /// description
+    fn set_display_name(&self, jenkins_client: &Jenkins, display_name: Option<&str>) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Description {
+            description: Option<String>,
+        }
+
+        let path = jenkins_client.url_to_path(self.url());
+        let current: Description = jenkins_client
+            .get_with_params(&path, [("tree", "description")])?
+            .json()?;
+        let _ = jenkins_client.post_with_body(
+            &build_page(jenkins_client, self.url(), "configSubmit")?,
+            serde_urlencoded::to_string([(
+                "json",
+                serde_json::json!({
+                    "displayName": display_name.unwrap_or_default(),
+                    "description": current.description.unwrap_or_default(),
+                })
+                .to_string(),
+            )])?,
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Toggle keeping a `Build` forever, protecting it from the build discarder
+    fn toggle_keep_forever(&self, jenkins_client: &Jenkins) -> Result<()> {
+        let _ = jenkins_client.post(&build_page(jenkins_client, self.url(), "toggleLogKeep")?)?;
+        Ok(())
+    }
+
+    /// Delete a `Build`
+    fn delete(&self, jenkins_client: &Jenkins) -> Result<()> {
+        let _ = jenkins_client.post(&build_page(jenkins_client, self.url(), "doDelete")?)?;
+        Ok(())
+    }
+}
+
+/// Path to a page of a build, from its URL
+fn build_page<'a>(jenkins_client: &Jenkins, url: &'a str, page: &'a str) -> Result<Path<'a>> {
+    jenkins_client
+        .url_to_path(url)
+        .map_build(page)
+        .ok_or_else(|| {
+            client::Error::InvalidUrl {
+                url: url.to_string(),
+                expected: client::error::ExpectedType::Build,
+            }
+            .into()
+        })
 }
 
 macro_rules! build_with_common_fields_and_impl {
@@ -387,3 +450,83 @@ build_with_common_fields_and_impl!(
 specialize!(CommonBuild => Build);
 
 impl CommonBuild {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestBuild(String);
+    impl Build for TestBuild {
+        type ParentJob = CommonJob;
+
+        fn url(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn can_edit_matrix_build_in_folder() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let build = TestBuild(format!(
+            "{}/job/team/job/matrix/os=linux/3/",
+            mockito::server_url()
+        ));
+
+        let description =
+            mockito::mock("POST", "/job/team/job/matrix/os=linux/3/submitDescription")
+                .match_body("description=deployed+1.2.3")
+                .create();
+        let _current = mockito::mock(
+            "GET",
+            "/job/team/job/matrix/os=linux/3/api/json?tree=description",
+        )
+        .with_body(r#"{"description": "deployed 1.2.3"}"#)
+        .create();
+        let display_name = mockito::mock("POST", "/job/team/job/matrix/os=linux/3/configSubmit")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "json".to_string(),
+                r#"{"description":"deployed 1.2.3","displayName":"release 1.2.3"}"#.to_string(),
+            ))
+            .create();
+        let keep = mockito::mock("POST", "/job/team/job/matrix/os=linux/3/toggleLogKeep").create();
+
+        assert!(build
+            .set_description(&jenkins_client, "deployed 1.2.3")
+            .is_ok());
+        assert!(build
+            .set_display_name(&jenkins_client, Some("release 1.2.3"))
+            .is_ok());
+        assert!(build.toggle_keep_forever(&jenkins_client).is_ok());
+        description.assert();
+        display_name.assert();
+        keep.assert();
+    }
+
+    #[test]
+    fn can_reset_display_name_and_delete_build() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let build = TestBuild(format!("{}/job/deleted/7/", mockito::server_url()));
+
+        let _current = mockito::mock("GET", "/job/deleted/7/api/json?tree=description")
+            .with_body(r#"{"description": null}"#)
+            .create();
+        let display_name = mockito::mock("POST", "/job/deleted/7/configSubmit")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "json".to_string(),
+                r#"{"description":"","displayName":""}"#.to_string(),
+            ))
+            .create();
+        let delete = mockito::mock("POST", "/job/deleted/7/doDelete").create();
+
+        assert!(build.set_display_name(&jenkins_client, None).is_ok());
+        assert!(build.delete(&jenkins_client).is_ok());
+        display_name.assert();
+        delete.assert();
+    }
+}
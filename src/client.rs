@@ -3,7 +3,7 @@
 use serde::{self, Deserialize};
 
 use crate::client_internals::path::{Name, Path as PrivatePath};
-use crate::client_internals::InternalAdvancedQueryParams;
+use crate::client_internals::{InternalAdvancedQueryParams, InternalXmlQueryParams};
 
 // pub use client_internals::path::Name;
 pub use crate::client_internals::AdvancedQuery;
@@ -19,7 +19,8 @@ pub enum Path<'a> {
     Home,
     /// Path to a view
     View {
-        /// The view name
+        /// The view name. Views nested in other views are named with the names of their
+        /// parents separated by `/`, like `parent/child`
         name: &'a str,
     },
     /// Path to a job
@@ -72,9 +73,7 @@ impl<'a> Into<PrivatePath<'a>> for Path<'a> {
     fn into(self) -> PrivatePath<'a> {
         match self {
             Path::Home => PrivatePath::Home,
-            Path::View { name } => PrivatePath::View {
-                name: Name::Name(name),
-            },
+            Path::View { name } => PrivatePath::for_view(name, |name| PrivatePath::View { name }),
             Path::Job {
                 name,
                 configuration,
@@ -173,4 +172,84 @@ impl super::Jenkins {
             )?
             .json()?)
     }
+
+    /// Get a `Path` from Jenkins through the XML API, optionally selecting a part of it with
+    /// an `xpath` expression. When the expression matches several nodes, a `wrapper` element
+    /// name must be given to wrap them in a single XML document
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate jenkins_api;
+    /// #
+    /// # use jenkins_api::JenkinsBuilder;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #    let jenkins = JenkinsBuilder::new("http://localhost:8080")
+    /// #        .with_user("user", Some("password"))
+    /// #        .build()?;
+    /// let _: String = jenkins.get_object_xml(
+    ///     jenkins_api::client::Path::Home,
+    ///     Some("//job/name"),
+    ///     Some("names"),
+    /// )?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn get_object_xml(
+        &self,
+        object: Path,
+        xpath: Option<&str>,
+        wrapper: Option<&str>,
+    ) -> Result<String> {
+        Ok(self
+            .get_xml_with_params(&object.into(), InternalXmlQueryParams { xpath, wrapper })?
+            .text()?)
+    }
+
+    /// Get a `Path` from Jenkins through the XML API like
+    /// [`get_object_xml`](#method.get_object_xml), and deserialize the XML returned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde;
+    /// #
+    /// # extern crate jenkins_api;
+    /// #
+    /// # use jenkins_api::JenkinsBuilder;
+    /// #
+    /// #[derive(Deserialize)]
+    /// struct Names {
+    ///     #[serde(rename = "name", default)]
+    ///     names: Vec<String>,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #    let jenkins = JenkinsBuilder::new("http://localhost:8080")
+    /// #        .with_user("user", Some("password"))
+    /// #        .build()?;
+    /// let _: Names = jenkins.get_object_xml_as(
+    ///     jenkins_api::client::Path::Home,
+    ///     Some("//job/name"),
+    ///     Some("names"),
+    /// )?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn get_object_xml_as<T>(
+        &self,
+        object: Path,
+        xpath: Option<&str>,
+        wrapper: Option<&str>,
+    ) -> Result<T>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        let xml = self.get_object_xml(object, xpath, wrapper)?;
+        Ok(quick_xml::de::from_str(&xml)?)
+    }
 }
@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use std::sync::RwLock;
 
 use reqwest::{self, blocking::Client, Url};
 
@@ -25,6 +26,7 @@ pub struct JenkinsBuilder {
     user: Option<User>,
     csrf_enabled: bool,
     depth: u8,
+    script_console_enabled: bool,
 }
 
 impl JenkinsBuilder {
@@ -41,6 +43,7 @@ impl JenkinsBuilder {
             user: None,
             csrf_enabled: true,
             depth: 1,
+            script_console_enabled: false,
         }
     }
 
@@ -56,10 +59,12 @@ impl JenkinsBuilder {
 
         Ok(Jenkins {
             url: self.url,
-            client: Client::builder().build()?,
+            client: Client::builder().cookie_store(true).build()?,
             user: self.user,
             csrf_enabled: self.csrf_enabled,
             depth: self.depth,
+            script_console_enabled: self.script_console_enabled,
+            server_version: RwLock::new(None),
         })
     }
 
@@ -84,6 +89,13 @@ impl JenkinsBuilder {
         self.depth = depth;
         self
     }
+
+    /// Allow running Groovy scripts through the script console. Scripts have full
+    /// access to Jenkins, this is disabled by default
+    pub fn enable_script_console(mut self) -> Self {
+        self.script_console_enabled = true;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -116,4 +128,13 @@ mod tests {
         assert_eq!(jenkins_client.user, None);
         assert_eq!(jenkins_client.csrf_enabled, false);
     }
+
+    #[test]
+    fn enable_script_console() {
+        let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL);
+        assert!(!jenkins_client.script_console_enabled);
+
+        let jenkins_client = jenkins_client.enable_script_console();
+        assert!(jenkins_client.script_console_enabled);
+    }
 }
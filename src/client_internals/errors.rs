@@ -38,10 +38,97 @@ pub enum Error {
         message: String,
     },
 
+    #[error("forbidden, missing permission {permission}")]
+    ///  Error thrown when the user is missing a permission
+    Forbidden {
+        /// Permission missing, like `Job/Build`
+        permission: String,
+    },
+
+    #[error("can't check permission {permission} on this object")]
+    ///  Error thrown when a permission can't be checked on an object
+    UnsupportedPermissionCheck {
+        /// Permission to check
+        permission: String,
+    },
+
     #[error("can't build a job remotely with parameters")]
     ///  Error when trying to remotely build a job with parameters
     UnsupportedBuildConfiguration,
 
+    #[error("unexpected response from Jenkins, missing {missing}")]
+    ///  Error thrown when a response from Jenkins doesn't contain what was expected
+    UnexpectedResponse {
+        /// What was missing in the response
+        missing: String,
+    },
+
+    #[error("script console is disabled, enable it with `JenkinsBuilder::enable_script_console`")]
+    ///  Error thrown when running a script without enabling the script console
+    ScriptConsoleDisabled,
+
+    #[error("script failed with {exception}: '{message}'")]
+    ///  Error thrown when a script run through the script console throws an exception
+    ScriptException {
+        /// Class of the exception thrown
+        exception: String,
+        /// Exception message
+        message: String,
+        /// Output of the script, including the stack trace
+        output: String,
+    },
+
+    #[error("Jenkins is not ready after waiting {waited:?}")]
+    ///  Error thrown when Jenkins is still not ready after waiting for it
+    NotReady {
+        /// Time waited
+        waited: std::time::Duration,
+    },
+
+    #[error("{feature} needs Jenkins {required} or newer, found {found}")]
+    ///  Error thrown when using a feature not supported by the version of Jenkins
+    UnsupportedVersion {
+        /// Feature used
+        feature: crate::server::Feature,
+        /// Minimum version of Jenkins supporting the feature
+        required: crate::server::Version,
+        /// Version of Jenkins
+        found: crate::server::Version,
+    },
+
+    #[error("invalid label expression '{expression}': {message}")]
+    ///  Error thrown when parsing an invalid label expression
+    InvalidLabelExpression {
+        /// Label expression
+        expression: String,
+        /// Description of the problem
+        message: String,
+    },
+
+    #[error("configuration of job '{job}' was changed while being updated")]
+    ///  Error thrown when the configuration of a job is detected to have changed between
+    ///  reading and updating it
+    ConfigConflict {
+        /// Job
+        job: String,
+    },
+
+    #[error("field '{field}' is not supported for jobs of type '{root}'")]
+    ///  Error thrown when patching a field not supported by a type of job
+    UnsupportedConfigField {
+        /// Field
+        field: &'static str,
+        /// Root element of the configuration
+        root: String,
+    },
+
+    #[error("invalid pipeline: {}", errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    ///  Error thrown when converting an invalid declarative pipeline
+    InvalidPipeline {
+        /// Errors found in the pipeline
+        errors: Vec<crate::jenkinsfile::JenkinsfileError>,
+    },
+
     #[error("can't do '{action}' on a {object_type} of type {variant_name}")]
     ///  Error when trying to do an action on an object not supporting it
     InvalidObjectType {
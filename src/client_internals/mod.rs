@@ -1,7 +1,7 @@
 //! Jenkins Client
 
-use std::fmt::Debug;
 use std::string::ToString;
+use std::sync::RwLock;
 
 use log::{debug, warn};
 use regex::Regex;
@@ -29,6 +29,13 @@ pub mod error {
     pub use super::errors::ExpectedType;
 }
 
+/// Get the HTTP status of an error, if it's an HTTP error
+pub(crate) fn status_of(error: &(dyn std::error::Error + 'static)) -> Option<StatusCode> {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+}
+
 #[derive(Debug, PartialEq)]
 struct User {
     username: String,
@@ -43,6 +50,9 @@ pub struct Jenkins {
     user: Option<User>,
     csrf_enabled: bool,
     pub(crate) depth: u8,
+    pub(crate) script_console_enabled: bool,
+    /// Version of the server once queried, `Some(None)` if the server doesn't announce it
+    pub(crate) server_version: RwLock<Option<Option<crate::server::Version>>>,
 }
 
 /// Advanced query parameters supported by Jenkins to control the amount of data retrieved
@@ -56,6 +66,13 @@ pub enum AdvancedQuery {
     Tree(TreeQueryParam),
 }
 
+/// Hidden type used to represent the query parameters of the XML API
+#[derive(Debug, Serialize)]
+pub(crate) struct InternalXmlQueryParams<'a> {
+    pub(crate) xpath: Option<&'a str>,
+    pub(crate) wrapper: Option<&'a str>,
+}
+
 /// Hidden type used to represent the AdvancedQueryParams as serializer doesn't support enums
 #[derive(Debug, Serialize)]
 pub(crate) struct InternalAdvancedQueryParams {
@@ -82,6 +99,10 @@ impl Jenkins {
         format!("{}{}/api/json", self.url, endpoint)
     }
 
+    pub(crate) fn url_api_xml(&self, endpoint: &str) -> String {
+        format!("{}{}/api/xml", self.url, endpoint)
+    }
+
     pub(crate) fn url(&self, endpoint: &str) -> String {
         format!("{}{}", self.url, endpoint)
     }
@@ -101,9 +122,45 @@ impl Jenkins {
         if status.is_client_error() || status.is_server_error() {
             warn!("got an error: {}", status);
         }
+        if status == StatusCode::FORBIDDEN {
+            // get the error before reading the body. In this case it can't be OK
+            let error = match response.error_for_status_ref() {
+                Ok(_) => unreachable!(),
+                Err(err) => err,
+            };
+
+            let body = response.text()?;
+
+            let re = Regex::new(r"is missing the ([^ ]+) permission").unwrap();
+            if let Some(permission) = re.captures(&body).and_then(|captures| captures.get(1)) {
+                return Err(Error::Forbidden {
+                    permission: permission.as_str().to_string(),
+                }
+                .into());
+            }
+            return Err(error.into());
+        }
         Ok(response.error_for_status()?)
     }
 
+    /// Send a GET query, only checking if it's authorized. Any status other than a
+    /// success or a refused authorization is an error
+    pub(crate) fn is_authorized(&self, path: &Path) -> Result<bool> {
+        let query = self.client.get(&self.url(&path.to_string()));
+        let response = self.send(query)?;
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => Ok(false),
+            status => match response.error_for_status() {
+                Err(error) => Err(error.into()),
+                Ok(_) => Err(Error::UnexpectedResponse {
+                    missing: format!("authorization status, got {}", status),
+                }
+                .into()),
+            },
+        }
+    }
+
     pub(crate) fn get(&self, path: &Path) -> Result<Response> {
         self.get_with_params(path, &[("depth", &self.depth.to_string())])
     }
@@ -116,6 +173,23 @@ impl Jenkins {
         Ok(Self::error_for_status(self.send(query)?)?)
     }
 
+    pub(crate) fn get_raw(&self, path: &Path) -> Result<Response> {
+        let query = self.client.get(&self.url(&path.to_string()));
+        Self::error_for_status(self.send(query)?)
+    }
+
+    pub(crate) fn get_xml_with_params<T: Serialize>(
+        &self,
+        path: &Path,
+        qps: T,
+    ) -> Result<Response> {
+        let query = self
+            .client
+            .get(&self.url_api_xml(&path.to_string()))
+            .query(&qps);
+        Self::error_for_status(self.send(query)?)
+    }
+
     pub(crate) fn post(&self, path: &Path) -> Result<Response> {
         let mut request_builder = self.client.post(&self.url(&path.to_string()));
 
@@ -124,21 +198,49 @@ impl Jenkins {
         Ok(Self::error_for_status(self.send(request_builder)?)?)
     }
 
-    pub(crate) fn post_with_body<T: Into<Body> + Debug>(
+    pub(crate) fn post_with_body<T: Into<Body>>(
+        &self,
+        path: &Path,
+        body: T,
+        qps: &[(&str, &str)],
+    ) -> Result<Response> {
+        self.post_with_body_and_content_type(
+            path,
+            body,
+            qps,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        )
+    }
+
+    pub(crate) fn post_xml<T: Into<Body>>(
+        &self,
+        path: &Path,
+        body: T,
+        qps: &[(&str, &str)],
+    ) -> Result<Response> {
+        self.post_with_body_and_content_type(
+            path,
+            body,
+            qps,
+            HeaderValue::from_static("application/xml"),
+        )
+    }
+
+    fn post_with_body_and_content_type<T: Into<Body>>(
         &self,
         path: &Path,
         body: T,
         qps: &[(&str, &str)],
+        content_type: HeaderValue,
     ) -> Result<Response> {
         let mut request_builder = self.client.post(&self.url(&path.to_string()));
 
         request_builder = self.add_csrf_to_request(request_builder)?;
 
-        request_builder = request_builder.header(
-            CONTENT_TYPE,
-            HeaderValue::from_static("application/x-www-form-urlencoded"),
-        );
-        debug!("{:?}", body);
+        request_builder = request_builder.header(CONTENT_TYPE, content_type);
+        // the body is not logged, as it can contain secrets like credentials
+        let body = body.into();
+        debug!("posting {} bytes", body.as_bytes().map_or(0, <[u8]>::len));
         request_builder = request_builder.query(qps).body(body);
         let response = self.send(request_builder)?;
 
@@ -200,6 +302,27 @@ impl Jenkins {
 #[cfg(test)]
 mod tests {
 
+    #[test]
+    fn can_get_forbidden_error() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("POST", "/job/myjob/build")
+            .with_status(403)
+            .with_body("<html><body>anonymous is missing the Job/Build permission</body></html>")
+            .create();
+
+        let response = jenkins_client.post(&super::Path::BuildJob {
+            name: super::Name::Name("myjob"),
+        });
+        match response.unwrap_err().downcast_ref::<super::Error>() {
+            Some(super::Error::Forbidden { permission }) => assert_eq!(permission, "Job/Build"),
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
     #[test]
     fn can_post_with_body() {
         let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
@@ -318,4 +441,31 @@ mod tests {
         assert_eq!(response.unwrap().text().unwrap(), "ok");
         mock.assert()
     }
+
+    #[test]
+    fn can_get_xml_with_xpath() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = mockito::mock("GET", "/xmlpath/api/xml?xpath=%2F%2Fname&wrapper=names")
+            .with_body("<names><name>a</name><name>b</name></names>")
+            .create();
+
+        let response = jenkins_client.get_xml_with_params(
+            &super::Path::Raw { path: "/xmlpath" },
+            super::InternalXmlQueryParams {
+                xpath: Some("//name"),
+                wrapper: Some("names"),
+            },
+        );
+
+        assert!(response.is_ok());
+        assert_eq!(
+            response.unwrap().text().unwrap(),
+            "<names><name>a</name><name>b</name></names>"
+        );
+        mock.assert()
+    }
 }
@@ -33,6 +33,13 @@ pub enum Path<'a> {
         job_name: Name<'a>,
         view_name: Name<'a>,
     },
+    CreateView,
+    ViewConfigXML {
+        name: Name<'a>,
+    },
+    DeleteView {
+        name: Name<'a>,
+    },
     Job {
         name: Name<'a>,
         configuration: Option<Name<'a>>,
@@ -52,11 +59,25 @@ pub enum Path<'a> {
     JobDisable {
         name: Name<'a>,
     },
+    DeleteJob {
+        name: Name<'a>,
+    },
+    JobPage {
+        name: Name<'a>,
+        page: &'a str,
+    },
+    CreateItem,
     Build {
         job_name: Name<'a>,
         number: build::BuildNumber,
         configuration: Option<Name<'a>>,
     },
+    BuildPage {
+        job_name: Name<'a>,
+        number: build::BuildNumber,
+        configuration: Option<Name<'a>>,
+        page: &'a str,
+    },
     ConsoleText {
         job_name: Name<'a>,
         number: build::BuildNumber,
@@ -80,14 +101,101 @@ pub enum Path<'a> {
         folder_name: Name<'a>,
         path: Box<Path<'a>>,
     },
+    InView {
+        view_name: Name<'a>,
+        path: Box<Path<'a>>,
+    },
     Computers,
     Computer {
         name: Name<'a>,
     },
+    CreateComputer,
+    DeleteComputer {
+        name: Name<'a>,
+    },
+    ToggleOfflineComputer {
+        name: Name<'a>,
+    },
+    DisconnectComputer {
+        name: Name<'a>,
+    },
+    LaunchComputer {
+        name: Name<'a>,
+    },
+    ComputerConfigXML {
+        name: Name<'a>,
+    },
+    ComputerJnlp {
+        name: Name<'a>,
+    },
+    ComputerPage {
+        name: Name<'a>,
+        page: &'a str,
+    },
+    Label {
+        name: Name<'a>,
+    },
+    OverallLoad,
+    QuietDown,
+    CancelQuietDown,
+    SafeRestart,
+    Restart,
+    SafeExit,
+    Reload,
+    PluginManager,
+    InstallNecessaryPlugins,
+    EnablePlugin {
+        name: Name<'a>,
+    },
+    DisablePlugin {
+        name: Name<'a>,
+    },
+    UninstallPlugin {
+        name: Name<'a>,
+    },
+    UpdateCenter,
+    CredentialsStore {
+        store: &'a str,
+    },
+    WhoAmI,
+    User {
+        id: Name<'a>,
+    },
+    People,
+    CredentialsDomain {
+        store: &'a str,
+        domain: Name<'a>,
+    },
+    CreateCredentials {
+        store: &'a str,
+        domain: Name<'a>,
+    },
+    Credential {
+        store: &'a str,
+        domain: Name<'a>,
+        id: Name<'a>,
+    },
+    CredentialConfigXML {
+        store: &'a str,
+        domain: Name<'a>,
+        id: Name<'a>,
+    },
+    DeleteCredential {
+        store: &'a str,
+        domain: Name<'a>,
+        id: Name<'a>,
+    },
+    ScriptText,
+    ComputerScriptText {
+        name: Name<'a>,
+    },
     Raw {
         path: &'a str,
     },
     CrumbIssuer,
+    PipelineModelConverter {
+        action: &'static str,
+    },
 }
 
 impl<'a> ToString for Path<'a> {
@@ -111,6 +219,9 @@ impl<'a> ToString for Path<'a> {
                 view_name.to_string(),
                 job_name.to_string()
             ),
+            Path::CreateView => "/createView".to_string(),
+            Path::ViewConfigXML { ref name } => format!("/view/{}/config.xml", name.to_string()),
+            Path::DeleteView { ref name } => format!("/view/{}/doDelete", name.to_string()),
             Path::Job {
                 ref name,
                 configuration: Some(ref configuration),
@@ -120,6 +231,9 @@ impl<'a> ToString for Path<'a> {
                 configuration: None,
             } => format!("/job/{}", name.to_string()),
             Path::BuildJob { ref name } => format!("/job/{}/build", name.to_string()),
+            Path::DeleteJob { ref name } => format!("/job/{}/doDelete", name.to_string()),
+            Path::JobPage { ref name, page } => format!("/job/{}/{}", name.to_string(), page),
+            Path::CreateItem => "/createItem".to_string(),
             Path::BuildJobWithParameters { ref name } => {
                 format!("/job/{}/buildWithParameters", name.to_string())
             }
@@ -141,6 +255,21 @@ impl<'a> ToString for Path<'a> {
                 configuration.to_string(),
                 number.to_string()
             ),
+            Path::BuildPage {
+                ref job_name,
+                ref number,
+                ref configuration,
+                page,
+            } => format!(
+                "{}/{}",
+                Path::Build {
+                    job_name: job_name.clone(),
+                    number: number.clone(),
+                    configuration: configuration.clone(),
+                }
+                .to_string(),
+                page
+            ),
             Path::ConsoleText {
                 ref job_name,
                 ref number,
@@ -222,10 +351,207 @@ impl<'a> ToString for Path<'a> {
                 ref folder_name,
                 ref path,
             } => format!("/job/{}{}", folder_name.to_string(), path.to_string()),
+            Path::InView {
+                ref view_name,
+                ref path,
+            } => format!("/view/{}{}", view_name.to_string(), path.to_string()),
             Path::Computers => "/computer/api/json".to_string(),
             Path::Computer { ref name } => format!("/computer/{}/api/json", name.to_string()),
+            Path::CreateComputer => "/computer/doCreateItem".to_string(),
+            Path::DeleteComputer { ref name } => {
+                format!("/computer/{}/doDelete", name.to_string())
+            }
+            Path::ToggleOfflineComputer { ref name } => {
+                format!("/computer/{}/toggleOffline", name.to_string())
+            }
+            Path::DisconnectComputer { ref name } => {
+                format!("/computer/{}/doDisconnect", name.to_string())
+            }
+            Path::LaunchComputer { ref name } => {
+                format!("/computer/{}/launchSlaveAgent", name.to_string())
+            }
+            Path::ComputerConfigXML { ref name } => {
+                format!("/computer/{}/config.xml", name.to_string())
+            }
+            Path::ComputerPage { ref name, page } => {
+                format!("/computer/{}/{}", name.to_string(), page)
+            }
+            Path::ComputerJnlp { ref name } => {
+                format!("/computer/{}/slave-agent.jnlp", name.to_string())
+            }
+            Path::Label { ref name } => format!("/label/{}", name.to_string()),
+            Path::OverallLoad => "/overallLoad".to_string(),
+            Path::QuietDown => "/quietDown".to_string(),
+            Path::CancelQuietDown => "/cancelQuietDown".to_string(),
+            Path::SafeRestart => "/safeRestart".to_string(),
+            Path::Restart => "/restart".to_string(),
+            Path::SafeExit => "/safeExit".to_string(),
+            Path::Reload => "/reload".to_string(),
+            Path::PluginManager => "/pluginManager".to_string(),
+            Path::InstallNecessaryPlugins => "/pluginManager/installNecessaryPlugins".to_string(),
+            Path::EnablePlugin { ref name } => {
+                format!("/pluginManager/plugin/{}/makeEnabled", name.to_string())
+            }
+            Path::DisablePlugin { ref name } => {
+                format!("/pluginManager/plugin/{}/makeDisabled", name.to_string())
+            }
+            Path::UninstallPlugin { ref name } => {
+                format!("/pluginManager/plugin/{}/doUninstall", name.to_string())
+            }
+            Path::UpdateCenter => "/updateCenter".to_string(),
+            Path::CredentialsStore { store } => format!("/credentials/store/{}", store),
+            Path::WhoAmI => "/whoAmI".to_string(),
+            Path::User { ref id } => format!("/user/{}", id.to_string()),
+            Path::People => "/asynchPeople".to_string(),
+            Path::CredentialsDomain { store, ref domain } => {
+                format!("/credentials/store/{}/domain/{}", store, domain.to_string())
+            }
+            Path::CreateCredentials { store, ref domain } => format!(
+                "/credentials/store/{}/domain/{}/createCredentials",
+                store,
+                domain.to_string()
+            ),
+            Path::Credential {
+                store,
+                ref domain,
+                ref id,
+            } => format!(
+                "/credentials/store/{}/domain/{}/credential/{}",
+                store,
+                domain.to_string(),
+                id.to_string()
+            ),
+            Path::CredentialConfigXML {
+                store,
+                ref domain,
+                ref id,
+            } => format!(
+                "/credentials/store/{}/domain/{}/credential/{}/config.xml",
+                store,
+                domain.to_string(),
+                id.to_string()
+            ),
+            Path::DeleteCredential {
+                store,
+                ref domain,
+                ref id,
+            } => format!(
+                "/credentials/store/{}/domain/{}/credential/{}/doDelete",
+                store,
+                domain.to_string(),
+                id.to_string()
+            ),
+            Path::ScriptText => "/scriptText".to_string(),
+            Path::ComputerScriptText { ref name } => {
+                format!("/computer/{}/scriptText", name.to_string())
+            }
             Path::Raw { path } => path.to_string(),
             Path::CrumbIssuer => "/crumbIssuer".to_string(),
+            Path::PipelineModelConverter { action } => {
+                format!("/pipeline-model-converter/{}", action)
+            }
+        }
+    }
+}
+
+impl<'a> Path<'a> {
+    /// Path to an action on a view, from the names of the nested views containing it
+    /// separated by `/`
+    pub(crate) fn for_view<F>(name: &'a str, action: F) -> Path<'a>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        let mut names = name.rsplit('/');
+        let mut path = action(Name::Name(names.next().unwrap_or(name)));
+        for parent in names {
+            path = Path::InView {
+                view_name: Name::Name(parent),
+                path: Box::new(path),
+            };
+        }
+        path
+    }
+
+    /// Path in nested folders, from the names of the folders separated by `/`
+    pub(crate) fn in_folders(folders: &'a str, path: Path<'a>) -> Path<'a> {
+        folders
+            .rsplit('/')
+            .filter(|folder| !folder.is_empty())
+            .fold(path, |path, folder| Path::InFolder {
+                folder_name: Name::Name(folder),
+                path: Box::new(path),
+            })
+    }
+
+    /// Path to an action on a job, from its full name with the names of the folders
+    /// containing it separated by `/`
+    pub(crate) fn for_job<F>(full_name: &'a str, action: F) -> Path<'a>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        match full_name.rsplit_once('/') {
+            Some((folders, name)) => Path::in_folders(folders, action(Name::Name(name))),
+            None => action(Name::Name(full_name)),
+        }
+    }
+
+    /// Transform the path to a job, possibly in folders, into the path to an action on
+    /// this job
+    pub(crate) fn map_job<F>(self, action: F) -> Option<Path<'a>>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        match self {
+            Path::Job {
+                name,
+                configuration: None,
+            } => Some(action(name)),
+            Path::InFolder { folder_name, path } => {
+                path.map_job(action).map(|path| Path::InFolder {
+                    folder_name,
+                    path: Box::new(path),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Transform the path to a build, possibly in folders, into the path to a page of
+    /// this build
+    pub(crate) fn map_build(self, page: &'a str) -> Option<Path<'a>> {
+        match self {
+            Path::Build {
+                job_name,
+                number,
+                configuration,
+            } => Some(Path::BuildPage {
+                job_name,
+                number,
+                configuration,
+                page,
+            }),
+            Path::InFolder { folder_name, path } => {
+                path.map_build(page).map(|path| Path::InFolder {
+                    folder_name,
+                    path: Box::new(path),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Transform the path to a view into the path to an action on this view
+    pub(crate) fn map_view<F>(self, action: F) -> Option<Path<'a>>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        match self {
+            Path::View { name } => Some(action(name)),
+            Path::InView { view_name, path } => path.map_view(action).map(|path| Path::InView {
+                view_name,
+                path: Box::new(path),
+            }),
+            _ => None,
         }
     }
 }
@@ -242,11 +568,18 @@ impl Jenkins {
             .filter(|c| c.1 == '/')
             .map(|c| c.0)
             .collect();
+        if slashes.len() < 2 {
+            return Path::Raw { path };
+        }
 
         match (&path[0..slashes[1]], slashes.len()) {
             ("/view", 3) => Path::View {
                 name: Name::UrlEncodedName(&path[6..(path.len() - 1)]),
             },
+            ("/view", n) if n > 3 && &path[slashes[2]..slashes[3]] == "/view" => Path::InView {
+                view_name: Name::UrlEncodedName(&path[6..slashes[2]]),
+                path: Box::new(self.url_to_path(&path[slashes[2]..])),
+            },
             ("/job", 3) => Path::Job {
                 name: Name::UrlEncodedName(&path[5..(path.len() - 1)]),
                 configuration: None,
@@ -311,6 +644,10 @@ impl Jenkins {
                     }
                 }
             }
+            ("/job", n) if n > 6 && &path[slashes[2]..slashes[3]] == "/job" => Path::InFolder {
+                folder_name: Name::UrlEncodedName(&path[5..slashes[2]]),
+                path: Box::new(self.url_to_path(&path[slashes[2]..])),
+            },
             ("/queue", 4) => Path::QueueItem {
                 id: path[(slashes[2] + 1)..(path.len() - 1)].parse().unwrap(),
             },
@@ -338,6 +675,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_parse_nested_view_path() {
+        let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
+
+        let path = jenkins_client.url_to_path("/view/parent/view/child/");
+        assert_eq!(
+            path,
+            Path::InView {
+                view_name: Name::UrlEncodedName("parent"),
+                path: Box::new(Path::View {
+                    name: Name::UrlEncodedName("child")
+                })
+            }
+        );
+        assert_eq!(path.to_string(), "/view/parent/view/child");
+    }
+
+    #[test]
+    fn can_build_nested_view_path() {
+        let path = Path::for_view("parent/child view", |name| Path::ViewConfigXML { name });
+        assert_eq!(
+            path.to_string(),
+            "/view/parent/view/child%20view/config.xml"
+        );
+    }
+
+    #[test]
+    fn can_parse_root_path() {
+        let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
+
+        let path_url = format!("{}/", JENKINS_URL);
+        let path = jenkins_client.url_to_path(&path_url);
+        assert_eq!(path, Path::Raw { path: "/" });
+    }
+
     #[test]
     fn can_parse_job_path() {
         let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
@@ -381,6 +753,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_parse_build_in_nested_folders_path() {
+        let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
+
+        let path = jenkins_client.url_to_path("/job/a/job/b/job/myjob/config/1/");
+        assert_eq!(
+            path,
+            Path::InFolder {
+                folder_name: Name::UrlEncodedName("a"),
+                path: Box::new(Path::InFolder {
+                    folder_name: Name::UrlEncodedName("b"),
+                    path: Box::new(Path::Build {
+                        job_name: Name::UrlEncodedName("myjob"),
+                        number: build::BuildNumber::Number(1),
+                        configuration: Some(Name::UrlEncodedName("config")),
+                    }),
+                }),
+            }
+        );
+    }
+
     #[test]
     fn can_parse_build_with_config_path() {
         let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
@@ -0,0 +1,467 @@
+//! Credentials stored by the Credentials plugin. Only metadata of the credentials can be
+//! read, secrets are never sent back by Jenkins
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{self, Deserialize, Serialize};
+
+use crate::client::Result;
+use crate::client_internals::{Name, Path};
+use crate::xml::{self, Element};
+use crate::Jenkins;
+
+/// Name of the global domain, containing credentials without restrictions
+pub const GLOBAL_DOMAIN: &str = "_";
+
+/// Location of a credentials store
+#[derive(Debug, Clone, Copy)]
+pub enum StoreLocation<'a> {
+    /// The system store of Jenkins
+    System,
+    /// The store of a folder, from the names of the folders separated by `/`
+    Folder(&'a str),
+}
+
+impl<'a> StoreLocation<'a> {
+    fn path<F>(self, action: F) -> Path<'a>
+    where
+        F: FnOnce(&'a str) -> Path<'a>,
+    {
+        match self {
+            StoreLocation::System => action("system"),
+            StoreLocation::Folder(folders) => Path::in_folders(folders, action("folder")),
+        }
+    }
+}
+
+/// Metadata of a credential
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Credential {
+    /// ID of the credential
+    pub id: String,
+    /// Type of credential, like `Username with password`
+    pub type_name: String,
+    /// Description of the credential
+    pub description: Option<String>,
+    /// Display name of the credential, secrets are masked
+    pub display_name: String,
+    /// Full name, with the store and domain
+    pub full_name: Option<String>,
+}
+
+/// A domain, grouping credentials
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialsDomain {
+    /// Name of the domain in URLs, `_` for the global domain
+    pub url_name: String,
+    /// Display name of the domain
+    pub display_name: String,
+    /// Description of the domain
+    pub description: Option<String>,
+    /// Is this the global domain
+    pub global: bool,
+    /// Credentials of the domain
+    #[serde(default)]
+    pub credentials: Vec<Credential>,
+}
+
+/// A credentials store
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialsStore {
+    /// Domains of the store, by name
+    pub domains: HashMap<String, CredentialsDomain>,
+}
+
+/// Scope of a credential
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CredentialScope {
+    /// Available to Jenkins and its jobs
+    Global,
+    /// Only available to Jenkins, like for connecting to agents
+    System,
+}
+
+/// Type of credential, with its secrets. Secrets are masked when debugging
+#[derive(Clone, Copy)]
+pub enum CredentialKind<'a> {
+    /// A username and a password
+    UsernamePassword {
+        /// Username
+        username: &'a str,
+        /// Password
+        password: &'a str,
+    },
+    /// A secret text, like a token, from the Plain Credentials plugin
+    SecretText {
+        /// Secret
+        secret: &'a str,
+    },
+    /// A username and a SSH private key, from the SSH Credentials plugin
+    SshPrivateKey {
+        /// Username
+        username: &'a str,
+        /// Private key
+        private_key: &'a str,
+        /// Passphrase of the private key
+        passphrase: Option<&'a str>,
+    },
+    /// A certificate from a PKCS#12 keystore
+    Certificate {
+        /// Content of the keystore, encoded in base64
+        keystore_base64: &'a str,
+        /// Password of the keystore
+        password: &'a str,
+    },
+}
+
+/// Replaces secrets when debugging
+const MASKED: &str = "***";
+
+impl<'a> fmt::Debug for CredentialKind<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CredentialKind::UsernamePassword { username, .. } => f
+                .debug_struct("UsernamePassword")
+                .field("username", &username)
+                .field("password", &MASKED)
+                .finish(),
+            CredentialKind::SecretText { .. } => f
+                .debug_struct("SecretText")
+                .field("secret", &MASKED)
+                .finish(),
+            CredentialKind::SshPrivateKey {
+                username,
+                passphrase,
+                ..
+            } => f
+                .debug_struct("SshPrivateKey")
+                .field("username", &username)
+                .field("private_key", &MASKED)
+                .field("passphrase", &passphrase.map(|_| MASKED))
+                .finish(),
+            CredentialKind::Certificate { .. } => f
+                .debug_struct("Certificate")
+                .field("keystore_base64", &MASKED)
+                .field("password", &MASKED)
+                .finish(),
+        }
+    }
+}
+
+/// Helper to create or update a credential. Secrets are masked when debugging
+#[derive(Clone)]
+pub struct CredentialBuilder<'a> {
+    id: &'a str,
+    description: &'a str,
+    scope: CredentialScope,
+    kind: CredentialKind<'a>,
+}
+
+impl<'a> fmt::Debug for CredentialBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CredentialBuilder")
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .field("scope", &self.scope)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl<'a> CredentialBuilder<'a> {
+    /// Create a new credential builder
+    pub fn new(id: &'a str, kind: CredentialKind<'a>) -> Self {
+        CredentialBuilder {
+            id,
+            description: "",
+            scope: CredentialScope::Global,
+            kind,
+        }
+    }
+
+    /// Set the description of the credential
+    pub fn with_description(mut self, description: &'a str) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Set the scope of the credential, default to `CredentialScope::Global`
+    pub fn with_scope(mut self, scope: CredentialScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// XML describing the credential, as expected by Jenkins
+    pub fn to_xml(&self) -> String {
+        let class = match self.kind {
+            CredentialKind::UsernamePassword { .. } => {
+                "com.cloudbees.plugins.credentials.impl.UsernamePasswordCredentialsImpl"
+            }
+            CredentialKind::SecretText { .. } => {
+                "org.jenkinsci.plugins.plaincredentials.impl.StringCredentialsImpl"
+            }
+            CredentialKind::SshPrivateKey { .. } => {
+                "com.cloudbees.jenkins.plugins.sshcredentials.impl.BasicSSHUserPrivateKey"
+            }
+            CredentialKind::Certificate { .. } => {
+                "com.cloudbees.plugins.credentials.impl.CertificateCredentialsImpl"
+            }
+        };
+        let mut credential = Element::new(class);
+        credential.push_child(Element::with_text(
+            "scope",
+            match self.scope {
+                CredentialScope::Global => "GLOBAL",
+                CredentialScope::System => "SYSTEM",
+            },
+        ));
+        credential.push_child(Element::with_text("id", self.id));
+        credential.push_child(Element::with_text("description", self.description));
+        match self.kind {
+            CredentialKind::UsernamePassword { username, password } => {
+                credential.push_child(Element::with_text("username", username));
+                credential.push_child(Element::with_text("password", password));
+            }
+            CredentialKind::SecretText { secret } => {
+                credential.push_child(Element::with_text("secret", secret));
+            }
+            CredentialKind::SshPrivateKey {
+                username,
+                private_key,
+                passphrase,
+            } => {
+                credential.push_child(Element::with_text("username", username));
+                credential.push_child(Element::with_text(
+                    "passphrase",
+                    passphrase.unwrap_or_default(),
+                ));
+                let mut source = Element::new("privateKeySource");
+                source.attributes.push((
+                    "class".to_string(),
+                    xml::escape("com.cloudbees.jenkins.plugins.sshcredentials.impl.BasicSSHUserPrivateKey$DirectEntryPrivateKeySource"),
+                ));
+                source.push_child(Element::with_text("privateKey", private_key));
+                credential.push_child(source);
+            }
+            CredentialKind::Certificate {
+                keystore_base64,
+                password,
+            } => {
+                credential.push_child(Element::with_text("password", password));
+                let mut source = Element::new("keyStoreSource");
+                source.attributes.push((
+                    "class".to_string(),
+                    xml::escape("com.cloudbees.plugins.credentials.impl.CertificateCredentialsImpl$UploadedKeyStoreSource"),
+                ));
+                source.push_child(Element::with_text("uploadedKeystoreBytes", keystore_base64));
+                credential.push_child(source);
+            }
+        }
+        credential.to_string()
+    }
+}
+
+impl Jenkins {
+    /// Get a credentials store, with its domains
+    pub fn get_credentials_store(&self, location: StoreLocation) -> Result<CredentialsStore> {
+        Ok(self
+            .get(&location.path(|store| Path::CredentialsStore { store }))?
+            .json()?)
+    }
+
+    /// Get a domain of a credentials store, with the metadata of its credentials
+    pub fn get_credentials_domain(
+        &self,
+        location: StoreLocation,
+        domain: &str,
+    ) -> Result<CredentialsDomain> {
+        Ok(self
+            .get(&location.path(|store| Path::CredentialsDomain {
+                store,
+                domain: Name::Name(domain),
+            }))?
+            .json()?)
+    }
+
+    /// Get the metadata of a credential
+    pub fn get_credential(
+        &self,
+        location: StoreLocation,
+        domain: &str,
+        id: &str,
+    ) -> Result<Credential> {
+        Ok(self
+            .get(&location.path(|store| Path::Credential {
+                store,
+                domain: Name::Name(domain),
+                id: Name::Name(id),
+            }))?
+            .json()?)
+    }
+
+    /// Create a credential
+    pub fn create_credential(
+        &self,
+        location: StoreLocation,
+        domain: &str,
+        credential: &CredentialBuilder,
+    ) -> Result<()> {
+        self.create_credential_from_xml(location, domain, &credential.to_xml())
+    }
+
+    /// Create a credential from its XML description
+    pub fn create_credential_from_xml(
+        &self,
+        location: StoreLocation,
+        domain: &str,
+        xml: &str,
+    ) -> Result<()> {
+        let _ = self.post_xml(
+            &location.path(|store| Path::CreateCredentials {
+                store,
+                domain: Name::Name(domain),
+            }),
+            xml.to_string(),
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Update a credential, replacing its secrets
+    pub fn update_credential(
+        &self,
+        location: StoreLocation,
+        domain: &str,
+        credential: &CredentialBuilder,
+    ) -> Result<()> {
+        self.update_credential_from_xml(location, domain, credential.id, &credential.to_xml())
+    }
+
+    /// Update a credential from its XML description
+    pub fn update_credential_from_xml(
+        &self,
+        location: StoreLocation,
+        domain: &str,
+        id: &str,
+        xml: &str,
+    ) -> Result<()> {
+        let _ = self.post_xml(
+            &location.path(|store| Path::CredentialConfigXML {
+                store,
+                domain: Name::Name(domain),
+                id: Name::Name(id),
+            }),
+            xml.to_string(),
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a credential
+    pub fn delete_credential(&self, location: StoreLocation, domain: &str, id: &str) -> Result<()> {
+        let _ = self.post(&location.path(|store| Path::DeleteCredential {
+            store,
+            domain: Name::Name(domain),
+            id: Name::Name(id),
+        }))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_build_credential_xml() {
+        let credential = CredentialBuilder::new(
+            "deploy",
+            CredentialKind::UsernamePassword {
+                username: "bot",
+                password: "p<ss",
+            },
+        )
+        .with_description("deploy bot");
+        assert_eq!(
+            credential.to_xml(),
+            "<com.cloudbees.plugins.credentials.impl.UsernamePasswordCredentialsImpl>\
+             <scope>GLOBAL</scope><id>deploy</id><description>deploy bot</description>\
+             <username>bot</username><password>p&lt;ss</password>\
+             </com.cloudbees.plugins.credentials.impl.UsernamePasswordCredentialsImpl>"
+        );
+    }
+
+    #[test]
+    fn secrets_are_masked_when_debugging() {
+        let kinds = [
+            CredentialKind::UsernamePassword {
+                username: "bot",
+                password: "hunter2",
+            },
+            CredentialKind::SecretText { secret: "hunter2" },
+            CredentialKind::SshPrivateKey {
+                username: "bot",
+                private_key: "hunter2",
+                passphrase: Some("hunter2"),
+            },
+            CredentialKind::Certificate {
+                keystore_base64: "hunter2",
+                password: "hunter2",
+            },
+        ];
+        for kind in &kinds {
+            let debug = format!("{:?}", CredentialBuilder::new("deploy", *kind));
+            assert!(debug.contains("deploy"));
+            assert!(debug.contains("***"));
+            assert!(!debug.contains("hunter2"), "secret in {}", debug);
+        }
+    }
+
+    #[test]
+    fn can_build_certificate_xml() {
+        let credential = CredentialBuilder::new(
+            "cert",
+            CredentialKind::Certificate {
+                keystore_base64: "a2V5c3RvcmU=",
+                password: "secret",
+            },
+        );
+        assert!(credential.to_xml().contains(
+            "<keyStoreSource class=\"com.cloudbees.plugins.credentials.impl.CertificateCredentialsImpl$UploadedKeyStoreSource\">\
+             <uploadedKeystoreBytes>a2V5c3RvcmU=</uploadedKeystoreBytes></keyStoreSource>"
+        ));
+    }
+
+    #[test]
+    fn can_create_credential_in_folder() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = mockito::mock(
+            "POST",
+            "/job/team/job/project/credentials/store/folder/domain/_/createCredentials",
+        )
+        .match_header("content-type", "application/xml")
+        .match_body(mockito::Matcher::Regex(
+            "<secret>token</secret>".to_string(),
+        ))
+        .create();
+
+        let credential =
+            CredentialBuilder::new("api-token", CredentialKind::SecretText { secret: "token" });
+        assert!(jenkins_client
+            .create_credential(
+                StoreLocation::Folder("team/project"),
+                GLOBAL_DOMAIN,
+                &credential
+            )
+            .is_ok());
+        mock.assert();
+    }
+}
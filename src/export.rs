@@ -0,0 +1,531 @@
+//! Export the jobs, views and nodes of Jenkins to a directory, and import them in
+//! another instance
+//!
+//! The directory mirrors the layout of `JENKINS_HOME`:
+//! * `jobs/<name>/config.xml`, with the jobs of a folder in `jobs/<folder>/jobs/<name>/config.xml`
+//! * `views/<name>/config.xml`, with the views nested in a `NestedView` in
+//!   `views/<parent>/<name>/config.xml`
+//! * `nodes/<name>/config.xml`
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path as FsPath;
+
+use log::warn;
+
+use crate::client::Result;
+use crate::client_internals::Path;
+use crate::helpers::Class;
+use crate::job::walker::WalkedJob;
+use crate::job::{OrganizationFolder, WorkflowMultiBranchProject};
+use crate::nodes::computer::MasterComputer;
+use crate::view::{AllView, NestedView, ShortView};
+use crate::Jenkins;
+
+const CONFIG_FILE: &str = "config.xml";
+const JOBS_DIR: &str = "jobs";
+const VIEWS_DIR: &str = "views";
+const NODES_DIR: &str = "nodes";
+
+/// Kind of item exported or imported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    /// A node
+    Node,
+    /// A job or a folder
+    Job,
+    /// A view
+    View,
+}
+
+/// An item exported or imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    /// Kind of the item
+    pub kind: ItemKind,
+    /// Name of the item, with the names of the folders containing it separated by `/`
+    /// for jobs
+    pub name: String,
+}
+
+/// Operation done to import an item
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    /// The item doesn't exist and is created
+    Create,
+    /// The item exists and its configuration is updated
+    Update,
+}
+
+/// An operation to import an item
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportAction {
+    /// Item imported
+    pub item: Item,
+    /// Operation done
+    pub operation: Operation,
+}
+
+/// Operations to import a directory, in the order they are done: nodes, then jobs with
+/// folders before their content, then views
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportPlan {
+    /// Operations to import the items
+    pub actions: Vec<ImportAction>,
+}
+
+/// Jobs created by scanning their parent, which are not exported
+fn is_computed_folder(class: Option<&str>) -> bool {
+    class == Some(WorkflowMultiBranchProject::with_class())
+        || class == Some(OrganizationFolder::with_class())
+}
+
+/// `_class` of a view
+fn view_class(view: &ShortView) -> Option<&str> {
+    view.extra_fields
+        .as_ref()
+        .and_then(|extra_fields| extra_fields.get("_class"))
+        .and_then(|class| class.as_str())
+}
+
+fn view_dir(dir: &FsPath, full_name: &str) -> std::path::PathBuf {
+    full_name
+        .split('/')
+        .fold(dir.join(VIEWS_DIR), |path, name| path.join(name))
+}
+
+fn job_dir(dir: &FsPath, full_name: &str) -> std::path::PathBuf {
+    full_name.split('/').fold(dir.to_path_buf(), |path, name| {
+        path.join(JOBS_DIR).join(name)
+    })
+}
+
+fn write_config(dir: &FsPath, config: &str) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(CONFIG_FILE), config)?;
+    Ok(())
+}
+
+/// Read the items in a directory with one sub-directory per item
+fn read_items(
+    dir: &FsPath,
+    kind: ItemKind,
+    prefix: &str,
+    items: &mut Vec<(Item, String)>,
+) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    entries.sort();
+    for entry in entries {
+        let config = entry.join(CONFIG_FILE);
+        if !config.is_file() {
+            continue;
+        }
+        let name = format!(
+            "{}{}",
+            prefix,
+            entry.file_name().unwrap_or_default().to_string_lossy()
+        );
+        items.push((
+            Item {
+                kind,
+                name: name.clone(),
+            },
+            fs::read_to_string(config)?,
+        ));
+        match kind {
+            ItemKind::Job => read_items(&entry.join(JOBS_DIR), kind, &format!("{}/", name), items)?,
+            ItemKind::View => read_items(&entry, kind, &format!("{}/", name), items)?,
+            ItemKind::Node => {}
+        }
+    }
+    Ok(())
+}
+
+impl Jenkins {
+    /// List the jobs with a configuration, excluding the jobs of multibranch projects and
+    /// organization folders that are created when scanning their parent
+    pub(crate) fn configured_jobs(&self) -> Result<Vec<WalkedJob>> {
+        let jobs = self.walk_jobs()?.collect::<Vec<_>>();
+        let computed = jobs
+            .iter()
+            .filter(|job| is_computed_folder(job.class.as_deref()))
+            .map(WalkedJob::full_name)
+            .collect::<HashSet<_>>();
+        Ok(jobs
+            .into_iter()
+            .filter(|job| {
+                !(1..=job.folders.len())
+                    .any(|depth| computed.contains(&job.folders[..depth].join("/")))
+            })
+            .collect())
+    }
+
+    /// List the views with their full names, like `parent/child` for the views nested in
+    /// a `NestedView`, excluding the view of all jobs that exists on every instance
+    fn configured_views(&self) -> Result<Vec<String>> {
+        let mut names = vec![];
+        self.collect_views(self.get_home()?.views, "", &mut names)?;
+        Ok(names)
+    }
+
+    fn collect_views(
+        &self,
+        views: Vec<ShortView>,
+        prefix: &str,
+        names: &mut Vec<String>,
+    ) -> Result<()> {
+        for view in views {
+            let class = view_class(&view);
+            if class == Some(AllView::with_class()) {
+                continue;
+            }
+            let name = format!("{}{}", prefix, view.name);
+            names.push(name.clone());
+            if class == Some(NestedView::with_class()) {
+                let nested = self.get_view(name.as_str())?.as_variant::<NestedView>()?;
+                self.collect_views(nested.views, &format!("{}/", name), names)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Export the config.xml files of the jobs, views and nodes to `dir`. Jobs of
+    /// multibranch projects and organization folders are not exported, as they are
+    /// created when scanning their parent. The view of all jobs is not exported
+    pub fn export_jobs<P: AsRef<FsPath>>(&self, dir: P) -> Result<Vec<Item>> {
+        let dir = dir.as_ref();
+        let mut exported = vec![];
+
+        for job in self.configured_jobs()? {
+            let full_name = job.full_name();
+            write_config(
+                &job_dir(dir, &full_name),
+                &self.get_job_config_xml(&full_name)?,
+            )?;
+            exported.push(Item {
+                kind: ItemKind::Job,
+                name: full_name,
+            });
+        }
+
+        for name in self.configured_views()? {
+            let config = self
+                .get_raw(&Path::for_view(&name, |name| Path::ViewConfigXML { name }))?
+                .text()?;
+            write_config(&view_dir(dir, &name), &config)?;
+            exported.push(Item {
+                kind: ItemKind::View,
+                name,
+            });
+        }
+
+        for computer in self.get_nodes()?.computers {
+            if computer.class.as_deref() == Some(MasterComputer::with_class()) {
+                continue;
+            }
+            let config = self.get_node_config_xml(computer.display_name.as_str())?;
+            write_config(&dir.join(NODES_DIR).join(&computer.display_name), &config)?;
+            exported.push(Item {
+                kind: ItemKind::Node,
+                name: computer.display_name,
+            });
+        }
+
+        Ok(exported)
+    }
+
+    /// Create a node from its config.xml file. Jenkins can't create a node from XML, so
+    /// an agent is created with the default configuration then updated, and deleted if
+    /// the update fails
+    fn create_node_from_xml(&self, name: &str, config: &str) -> Result<()> {
+        self.permanent_agent_builder(name).send()?;
+        if let Err(error) = self.update_node_config_xml(name, config) {
+            if let Err(delete_error) = self.delete_node(name) {
+                warn!(
+                    "could not delete node '{}' after failing to configure it: {}",
+                    name, delete_error
+                );
+            }
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    fn read_import(&self, dir: &FsPath) -> Result<Vec<(ImportAction, String)>> {
+        let mut items = vec![];
+        read_items(&dir.join(NODES_DIR), ItemKind::Node, "", &mut items)?;
+        read_items(&dir.join(JOBS_DIR), ItemKind::Job, "", &mut items)?;
+        read_items(&dir.join(VIEWS_DIR), ItemKind::View, "", &mut items)?;
+        // jobs and views are read depth first, sort them so that all folders and nested
+        // views come before their content
+        items.sort_by_key(|(item, _)| (item.kind as u8, item.name.matches('/').count()));
+
+        let existing_jobs = self
+            .walk_jobs()?
+            .map(|job| job.full_name())
+            .collect::<HashSet<_>>();
+        let existing_views = self
+            .get_home()?
+            .views
+            .into_iter()
+            .map(|view| view.name)
+            .collect::<HashSet<_>>();
+        let existing_nodes = self
+            .get_nodes()?
+            .computers
+            .into_iter()
+            .map(|computer| computer.display_name)
+            .collect::<HashSet<_>>();
+
+        Ok(items
+            .into_iter()
+            .map(|(item, config)| {
+                let exists = match item.kind {
+                    ItemKind::Node => existing_nodes.contains(&item.name),
+                    ItemKind::Job => existing_jobs.contains(&item.name),
+                    // nested views are in the configuration of their parent, so they
+                    // exist once their parent is imported
+                    ItemKind::View => {
+                        item.name.contains('/') || existing_views.contains(&item.name)
+                    }
+                };
+                let operation = if exists {
+                    Operation::Update
+                } else {
+                    Operation::Create
+                };
+                (ImportAction { item, operation }, config)
+            })
+            .collect())
+    }
+
+    /// Plan the import of a directory exported with `export_jobs`, without changing
+    /// anything
+    pub fn plan_import<P: AsRef<FsPath>>(&self, dir: P) -> Result<ImportPlan> {
+        Ok(ImportPlan {
+            actions: self
+                .read_import(dir.as_ref())?
+                .into_iter()
+                .map(|(action, _)| action)
+                .collect(),
+        })
+    }
+
+    /// Import a directory exported with `export_jobs`, creating the missing items and
+    /// updating the existing ones
+    pub fn import_jobs<P: AsRef<FsPath>>(&self, dir: P) -> Result<ImportPlan> {
+        let actions = self.read_import(dir.as_ref())?;
+        for (action, config) in &actions {
+            let name = action.item.name.as_str();
+            match (action.item.kind, action.operation) {
+                (ItemKind::Node, Operation::Create) => self.create_node_from_xml(name, config)?,
+                (ItemKind::Node, Operation::Update) => self.update_node_config_xml(name, config)?,
+                (ItemKind::Job, Operation::Create) => self.create_job_from_xml(name, config)?,
+                (ItemKind::Job, Operation::Update) => self.update_job_config_xml(name, config)?,
+                (ItemKind::View, Operation::Create) => self.create_view_from_xml(name, config)?,
+                (ItemKind::View, Operation::Update) => {
+                    let _ = self.post_xml(
+                        &Path::for_view(name, |name| Path::ViewConfigXML { name }),
+                        config.to_string(),
+                        &[],
+                    )?;
+                }
+            }
+        }
+        Ok(ImportPlan {
+            actions: actions.into_iter().map(|(action, _)| action).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_plan_import_in_dependency_order() {
+        let dir = std::env::temp_dir().join(format!(
+            "jenkins-api-plan-import-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        write_config(&job_dir(&dir, "team"), "<folder/>").unwrap();
+        write_config(&job_dir(&dir, "team/app"), "<project/>").unwrap();
+        write_config(&job_dir(&dir, "team/app/nested"), "<project/>").unwrap();
+        write_config(&job_dir(&dir, "zzz"), "<project/>").unwrap();
+        write_config(&dir.join(VIEWS_DIR).join("all"), "<view/>").unwrap();
+
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let _jobs = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/api/json\?tree=jobs".to_string()),
+        )
+        .with_body(r#"{"jobs": [{"name": "team", "url": "/job/team/", "jobs": []}]}"#)
+        .create();
+        let _nodes = mockito::mock("GET", "/computer/api/json/api/json?depth=1")
+            .with_body(r#"{"displayName": "nodes", "busyExecutors": 0, "totalExecutors": 0, "computer": []}"#)
+            .create();
+        let _home = mockito::mock("GET", "/api/json?depth=1")
+            .with_body(
+                r#"{"mode": "NORMAL", "nodeDescription": "", "nodeName": "", "numExecutors": 1,
+                    "jobs": [], "quietingDown": false, "slaveAgentPort": 0, "useCrumbs": false,
+                    "useSecurity": false, "views": [{"name": "all", "url": "/"}]}"#,
+            )
+            .create();
+
+        let plan = jenkins_client.plan_import(&dir).unwrap();
+
+        assert_eq!(
+            plan.actions
+                .iter()
+                .map(|action| (action.item.name.as_str(), action.operation))
+                .collect::<Vec<_>>(),
+            vec![
+                ("team", Operation::Update),
+                ("zzz", Operation::Create),
+                ("team/app", Operation::Create),
+                ("team/app/nested", Operation::Create),
+                ("all", Operation::Update),
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn deletes_node_failing_to_be_configured() {
+        let dir = std::env::temp_dir().join(format!(
+            "jenkins-api-import-node-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        write_config(&dir.join(NODES_DIR).join("agent"), "<slave/>").unwrap();
+
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/import-node", mockito::server_url()))
+                .disable_csrf()
+                .build()
+                .unwrap();
+        let _jobs = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/import-node/api/json\?tree=jobs".to_string()),
+        )
+        .with_body(r#"{"jobs": []}"#)
+        .create();
+        let _nodes = mockito::mock("GET", "/import-node/computer/api/json/api/json?depth=1")
+            .with_body(r#"{"displayName": "nodes", "busyExecutors": 0, "totalExecutors": 0, "computer": []}"#)
+            .create();
+        let _home = mockito::mock("GET", "/import-node/api/json?depth=1")
+            .with_body(
+                r#"{"mode": "NORMAL", "nodeDescription": "", "nodeName": "", "numExecutors": 1,
+                    "jobs": [], "quietingDown": false, "slaveAgentPort": 0, "useCrumbs": false,
+                    "useSecurity": false, "views": [{"name": "all", "url": "/import-node/"}]}"#,
+            )
+            .create();
+        let created = mockito::mock("POST", "/import-node/computer/doCreateItem").create();
+        let _configured = mockito::mock("POST", "/import-node/computer/agent/config.xml")
+            .with_status(400)
+            .create();
+        let deleted = mockito::mock("POST", "/import-node/computer/agent/doDelete").create();
+
+        assert!(jenkins_client.import_jobs(&dir).is_err());
+        created.assert();
+        deleted.assert();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn can_export_nested_views() {
+        let dir = std::env::temp_dir().join(format!(
+            "jenkins-api-nested-views-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/nested-views", mockito::server_url()))
+                .build()
+                .unwrap();
+
+        let _jobs = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/nested-views/api/json\?tree=jobs".to_string()),
+        )
+        .with_body(r#"{"jobs": []}"#)
+        .create();
+        let _nodes = mockito::mock("GET", "/nested-views/computer/api/json/api/json?depth=1")
+            .with_body(r#"{"displayName": "nodes", "busyExecutors": 0, "totalExecutors": 0, "computer": []}"#)
+            .create();
+        let _home = mockito::mock("GET", "/nested-views/api/json?depth=1")
+            .with_body(
+                r#"{"mode": "NORMAL", "nodeDescription": "", "nodeName": "", "numExecutors": 1,
+                    "jobs": [], "quietingDown": false, "slaveAgentPort": 0, "useCrumbs": false,
+                    "useSecurity": false, "views": [
+                        {"_class": "hudson.model.AllView", "name": "all", "url": "/nested-views/"},
+                        {"_class": "hudson.plugins.nested_view.NestedView", "name": "team", "url": "/nested-views/view/team/"}
+                    ]}"#,
+            )
+            .create();
+        let _team = mockito::mock("GET", "/nested-views/view/team/api/json?depth=1")
+            .with_body(
+                r#"{"_class": "hudson.plugins.nested_view.NestedView", "name": "team",
+                    "url": "/nested-views/view/team/", "views": [
+                        {"_class": "hudson.model.ListView", "name": "app", "url": "/nested-views/view/team/view/app/"}
+                    ]}"#,
+            )
+            .create();
+        let _team_config = mockito::mock("GET", "/nested-views/view/team/config.xml")
+            .with_body("<hudson.plugins.nested__view.NestedView/>")
+            .create();
+        let _app_config = mockito::mock("GET", "/nested-views/view/team/view/app/config.xml")
+            .with_body("<hudson.model.ListView/>")
+            .create();
+
+        let exported = jenkins_client.export_jobs(&dir).unwrap();
+        assert_eq!(
+            exported,
+            vec![
+                Item {
+                    kind: ItemKind::View,
+                    name: "team".to_string(),
+                },
+                Item {
+                    kind: ItemKind::View,
+                    name: "team/app".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("views/team/app/config.xml")).unwrap(),
+            "<hudson.model.ListView/>"
+        );
+
+        let plan = jenkins_client.plan_import(&dir).unwrap();
+        assert_eq!(
+            plan.actions
+                .iter()
+                .map(|action| (action.item.name.as_str(), action.operation))
+                .collect::<Vec<_>>(),
+            vec![("team", Operation::Update), ("team/app", Operation::Update)]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
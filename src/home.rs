@@ -1,14 +1,20 @@
 //! Jenkins Home, describing state of the master
 
-use serde::Deserialize;
+use std::time::{Duration, Instant};
 
-use crate::client_internals::{Path, Result};
+use log::debug;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::client;
+use crate::client_internals::{status_of, Path, Result};
 use crate::job::ShortJob;
+use crate::server::Feature;
 use crate::view::ShortView;
 use crate::Jenkins;
 
 /// Describe how Jenkins allocates jobs to agents
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Mode {
     /// Any job can be started on this node
@@ -144,9 +150,246 @@ pub struct Home {
     pub views: Vec<ShortView>,
 }
 
+/// Interval between two checks when waiting for Jenkins to be ready
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuietingDown {
+    quieting_down: bool,
+}
+
 impl Jenkins {
     /// Get Jenkins `Home`
     pub fn get_home(&self) -> Result<Home> {
         Ok(self.get(&Path::Home)?.json()?)
     }
+
+    /// Put Jenkins in quiet mode, to prepare for a restart. No new builds will be started.
+    /// Giving a reason fails with `UnsupportedVersion` on Jenkins older than 2.267
+    pub fn quiet_down(&self, reason: Option<&str>) -> Result<()> {
+        let _ = match reason {
+            Some(reason) => {
+                self.require(Feature::QuietDownReason)?;
+                self.post_with_body(&Path::QuietDown, "", &[("reason", reason)])?
+            }
+            None => self.post(&Path::QuietDown)?,
+        };
+        Ok(())
+    }
+
+    /// Cancel the quiet mode
+    pub fn cancel_quiet_down(&self) -> Result<()> {
+        let _ = self.post(&Path::CancelQuietDown)?;
+        Ok(())
+    }
+
+    /// Restart Jenkins once no builds are running
+    pub fn safe_restart(&self) -> Result<()> {
+        self.post_while_going_down(&Path::SafeRestart)
+    }
+
+    /// Restart Jenkins immediately, without waiting for running builds to finish
+    pub fn restart(&self) -> Result<()> {
+        self.post_while_going_down(&Path::Restart)
+    }
+
+    /// Shut down Jenkins once no builds are running
+    pub fn safe_exit(&self) -> Result<()> {
+        self.post_while_going_down(&Path::SafeExit)
+    }
+
+    /// Reload the configuration from disk
+    pub fn reload_configuration(&self) -> Result<()> {
+        self.post_while_going_down(&Path::Reload)
+    }
+
+    /// Post an action after which Jenkins is unavailable. The redirect following it may
+    /// get a `503 Service Unavailable`, which is expected
+    fn post_while_going_down(&self, path: &Path) -> Result<()> {
+        match self.post(path) {
+            Ok(_) => Ok(()),
+            Err(error) if status_of(error.as_ref()) == Some(StatusCode::SERVICE_UNAVAILABLE) => {
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Check if Jenkins answers queries, and if it's quieting down. `None` if it doesn't
+    /// answer yet. Fails if the user is not authorized
+    fn quieting_down_state(&self) -> Result<Option<bool>> {
+        match self.get_with_params(&Path::Home, [("tree", "quietingDown")]) {
+            Ok(response) => Ok(Some(response.json::<QuietingDown>()?.quieting_down)),
+            Err(error)
+                if status_of(error.as_ref()) == Some(StatusCode::UNAUTHORIZED)
+                    || status_of(error.as_ref()) == Some(StatusCode::FORBIDDEN) =>
+            {
+                Err(error)
+            }
+            Err(error) => {
+                debug!("Jenkins is not ready: {}", error);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Check the state of Jenkins every second until `done` returns true for it. Fails
+    /// with `NotReady` after `timeout`
+    fn wait_for_state<F>(&self, start: Instant, timeout: Duration, done: F) -> Result<()>
+    where
+        F: Fn(Option<bool>) -> bool,
+    {
+        loop {
+            if done(self.quieting_down_state()?) {
+                return Ok(());
+            }
+            let waited = start.elapsed();
+            if waited >= timeout {
+                return Err(client::Error::NotReady { waited }.into());
+            }
+            std::thread::sleep(READY_POLL_INTERVAL.min(timeout - waited));
+        }
+    }
+
+    /// Wait until Jenkins is ready to answer queries, for example when starting,
+    /// checking every second. Fails if Jenkins is not ready after `timeout`, or if
+    /// the user is not authorized. Use `wait_until_restarted` after a restart, as
+    /// Jenkins may still answer before going down
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<()> {
+        self.wait_for_state(Instant::now(), timeout, |state| state.is_some())
+    }
+
+    /// Wait until Jenkins restarted after `restart` or `safe_restart`, checking every
+    /// second. Jenkins must first stop answering queries or be quieting down, then be
+    /// ready and no longer quieting down. Fails if Jenkins is not restarted after
+    /// `timeout`, or if the user is not authorized
+    pub fn wait_until_restarted(&self, timeout: Duration) -> Result<()> {
+        let start = Instant::now();
+        self.wait_for_state(start, timeout, |state| state != Some(false))?;
+        self.wait_for_state(start, timeout, |state| state == Some(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    fn mock_version(prefix: &str, version: &str) -> mockito::Mock {
+        mockito::mock(
+            "GET",
+            format!("{}/api/json?tree=useSecurity%2CuseCrumbs", prefix).as_str(),
+        )
+        .with_header("X-Jenkins", version)
+        .with_body(r#"{"useSecurity": true, "useCrumbs": false}"#)
+        .create()
+    }
+
+    #[test]
+    fn can_quiet_down_with_reason() {
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/quiet", mockito::server_url()))
+                .disable_csrf()
+                .build()
+                .unwrap();
+
+        let _version = mock_version("/quiet", "2.289.1");
+        let mock = mockito::mock("POST", "/quiet/quietDown?reason=maintenance").create();
+
+        assert!(jenkins_client.quiet_down(Some("maintenance")).is_ok());
+        mock.assert();
+    }
+
+    #[test]
+    fn fails_to_quiet_down_with_reason_on_old_versions() {
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/old-quiet", mockito::server_url()))
+                .disable_csrf()
+                .build()
+                .unwrap();
+
+        let _version = mock_version("/old-quiet", "2.263.1");
+        let mock = mockito::mock("POST", "/old-quiet/quietDown?reason=maintenance")
+            .expect(0)
+            .create();
+
+        match jenkins_client
+            .quiet_down(Some("maintenance"))
+            .unwrap_err()
+            .downcast_ref::<crate::client::Error>()
+        {
+            Some(crate::client::Error::UnsupportedVersion { .. }) => {}
+            other => panic!("unexpected error {:?}", other),
+        }
+        mock.assert();
+    }
+
+    fn waited(error: &(dyn std::error::Error + 'static)) -> Duration {
+        match error.downcast_ref::<crate::client::Error>() {
+            Some(crate::client::Error::NotReady { waited }) => *waited,
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_wait_until_ready() {
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/ready", mockito::server_url()))
+                .build()
+                .unwrap();
+
+        let _mock = mockito::mock("GET", "/ready/api/json?tree=quietingDown")
+            .with_body(r#"{"quietingDown": false}"#)
+            .create();
+
+        assert!(jenkins_client
+            .wait_until_ready(Duration::from_secs(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn can_timeout_waiting_until_ready() {
+        let jenkins_client = crate::JenkinsBuilder::new("http://127.0.0.1:1")
+            .build()
+            .unwrap();
+
+        let error = jenkins_client
+            .wait_until_ready(Duration::from_millis(10))
+            .unwrap_err();
+        assert!(waited(error.as_ref()) >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn waits_for_restart_to_begin() {
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/not-restarted", mockito::server_url()))
+                .build()
+                .unwrap();
+
+        let _mock = mockito::mock("GET", "/not-restarted/api/json?tree=quietingDown")
+            .with_body(r#"{"quietingDown": false}"#)
+            .create();
+
+        // Jenkins still answers as before the restart, it has not restarted yet
+        let error = jenkins_client
+            .wait_until_restarted(Duration::from_millis(10))
+            .unwrap_err();
+        assert!(waited(error.as_ref()) >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn waits_for_restart_to_end() {
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/quieting-down", mockito::server_url()))
+                .build()
+                .unwrap();
+
+        let _mock = mockito::mock("GET", "/quieting-down/api/json?tree=quietingDown")
+            .with_body(r#"{"quietingDown": true}"#)
+            .create();
+
+        assert!(jenkins_client
+            .wait_until_restarted(Duration::from_millis(10))
+            .is_err());
+    }
 }
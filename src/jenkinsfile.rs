@@ -0,0 +1,355 @@
+//! Validate declarative pipelines, and convert them to and from their JSON representation
+//!
+//! This uses the endpoints of the Pipeline: Declarative plugin, so pipelines are checked
+//! against the plugins installed on the server
+
+use std::fmt;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::client::{self, Result};
+use crate::client_internals::Path;
+use crate::Jenkins;
+
+/// Start of the output of the validation when the pipeline is valid
+const VALIDATED: &str = "Jenkinsfile successfully validated.";
+/// Start of the output of the validation when the pipeline is invalid
+const ERRORS_HEADER: &str = "Errors encountered validating Jenkinsfile:";
+/// Start and end of the output of the validation when the Jenkinsfile is not a
+/// declarative pipeline
+const NOT_DECLARATIVE_START: &str = "Jenkinsfile content";
+const NOT_DECLARATIVE_END: &str = "did not contain the 'pipeline' step";
+
+/// An error found in a declarative pipeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct JenkinsfileError {
+    /// Description of the error
+    pub message: String,
+    /// Line of the error, starting at 1
+    pub line: Option<u32>,
+    /// Column of the error, starting at 1
+    pub column: Option<u32>,
+}
+
+impl fmt::Display for JenkinsfileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {}: {}", line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Parse the errors in the output of the validation, each followed by the source of its
+/// line. Fails if the output is not a validation result, like a login page
+fn errors_in_output(output: &str) -> Result<Vec<JenkinsfileError>> {
+    let output = output.trim();
+    if output.starts_with(VALIDATED) {
+        return Ok(vec![]);
+    }
+    if output.starts_with(NOT_DECLARATIVE_START) && output.ends_with(NOT_DECLARATIVE_END) {
+        return Ok(vec![JenkinsfileError {
+            message: output.to_string(),
+            line: None,
+            column: None,
+        }]);
+    }
+    if !output.starts_with(ERRORS_HEADER) {
+        return Err(client::Error::UnexpectedResponse {
+            missing: "result of the validation".to_string(),
+        }
+        .into());
+    }
+    // an error is like `WorkflowScript: 5: Expected a step @ line 5, column 13.`, the
+    // number after `WorkflowScript` being the line of the error
+    let re = Regex::new(r"(?m)^WorkflowScript: (-?\d+): (.*?)(?: @ line (\d+), column (\d+)\.)?$")
+        .unwrap();
+    let errors = re
+        .captures_iter(output)
+        .map(|captures| {
+            let number = |index: usize| {
+                captures
+                    .get(index)
+                    .and_then(|v| v.as_str().parse::<u32>().ok())
+                    .filter(|number| *number > 0)
+            };
+            JenkinsfileError {
+                message: captures
+                    .get(2)
+                    .map(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                line: number(3).or_else(|| number(1)),
+                column: number(4),
+            }
+        })
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(vec![JenkinsfileError {
+            message: output.trim_start_matches(ERRORS_HEADER).trim().to_string(),
+            line: None,
+            column: None,
+        }])
+    } else {
+        Ok(errors)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverterResponse {
+    data: ConverterData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverterData {
+    result: String,
+    #[serde(default)]
+    errors: Vec<ConverterError>,
+    json: Option<serde_json::Value>,
+    jenkinsfile: Option<String>,
+}
+
+/// Error of a conversion, either from the parser of Jenkinsfiles with a location, or
+/// from the validation of a JSON pipeline with one or more messages
+#[derive(Debug, Deserialize)]
+struct ConverterError {
+    message: Option<String>,
+    error: Option<serde_json::Value>,
+    line: Option<u32>,
+    column: Option<u32>,
+}
+
+impl From<ConverterError> for JenkinsfileError {
+    fn from(error: ConverterError) -> Self {
+        let message = match (error.message, error.error) {
+            (Some(message), _) => message,
+            (None, Some(serde_json::Value::String(message))) => message,
+            (None, Some(serde_json::Value::Array(messages))) => messages
+                .iter()
+                .map(|message| {
+                    message
+                        .as_str()
+                        .map_or_else(|| message.to_string(), str::to_string)
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            (None, Some(other)) => other.to_string(),
+            (None, None) => "unknown error".to_string(),
+        };
+        JenkinsfileError {
+            message,
+            line: error.line,
+            column: error.column,
+        }
+    }
+}
+
+impl Jenkins {
+    fn convert_pipeline(
+        &self,
+        action: &'static str,
+        field: &str,
+        value: &str,
+    ) -> Result<ConverterData> {
+        let response: ConverterResponse = self
+            .post_with_body(
+                &Path::PipelineModelConverter { action },
+                serde_urlencoded::to_string([(field, value)])?,
+                &[],
+            )?
+            .json()?;
+        if response.data.result == "success" {
+            Ok(response.data)
+        } else {
+            Err(client::Error::InvalidPipeline {
+                errors: response
+                    .data
+                    .errors
+                    .into_iter()
+                    .map(JenkinsfileError::from)
+                    .collect(),
+            }
+            .into())
+        }
+    }
+
+    /// Validate a declarative pipeline against the plugins installed on the server, and
+    /// get the errors found, with their location when known. A valid pipeline has no
+    /// errors. Fails with `UnexpectedResponse` if the server doesn't answer with the
+    /// result of the validation
+    ///
+    /// ```rust
+    ///# extern crate jenkins_api;
+    ///#
+    ///# use jenkins_api::JenkinsBuilder;
+    ///#
+    ///# fn example_function() {
+    ///     let jenkins = JenkinsBuilder::new("http://localhost:8080")
+    ///         .with_user("user", Some("password"))
+    ///         .build()
+    ///         .unwrap();
+    ///     let jenkinsfile = std::fs::read_to_string("Jenkinsfile").unwrap();
+    ///     for error in jenkins.validate_jenkinsfile(&jenkinsfile).unwrap() {
+    ///         println!("{}", error);
+    ///     }
+    ///# }
+    /// ```
+    pub fn validate_jenkinsfile(&self, jenkinsfile: &str) -> Result<Vec<JenkinsfileError>> {
+        let output = self
+            .post_with_body(
+                &Path::PipelineModelConverter { action: "validate" },
+                serde_urlencoded::to_string([("jenkinsfile", jenkinsfile)])?,
+                &[],
+            )?
+            .text()?;
+        errors_in_output(&output)
+    }
+
+    /// Convert a declarative pipeline to its JSON representation. Fails with
+    /// `InvalidPipeline` if the pipeline is invalid
+    pub fn jenkinsfile_to_json(&self, jenkinsfile: &str) -> Result<serde_json::Value> {
+        self.convert_pipeline("toJson", "jenkinsfile", jenkinsfile)?
+            .json
+            .ok_or_else(|| {
+                client::Error::UnexpectedResponse {
+                    missing: "json".to_string(),
+                }
+                .into()
+            })
+    }
+
+    /// Convert the JSON representation of a declarative pipeline to a Jenkinsfile. Fails
+    /// with `InvalidPipeline` if the pipeline is invalid
+    pub fn json_to_jenkinsfile(&self, json: &serde_json::Value) -> Result<String> {
+        self.convert_pipeline("toJenkinsfile", "json", &json.to_string())?
+            .jenkinsfile
+            .ok_or_else(|| {
+                client::Error::UnexpectedResponse {
+                    missing: "jenkinsfile".to_string(),
+                }
+                .into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_validation_errors() {
+        assert_eq!(
+            errors_in_output("Jenkinsfile successfully validated.\n").unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            errors_in_output(
+                r#"Errors encountered validating Jenkinsfile:
+WorkflowScript: 6: Unknown stage section "step". @ line 6, column 9.
+           stage('Build') {
+           ^
+
+WorkflowScript: 2: Invalid agent type "docker" specified. Must be one of [any, label, none]
+
+WorkflowScript: -1: Unexpected error
+"#
+            )
+            .unwrap(),
+            vec![
+                JenkinsfileError {
+                    message: r#"Unknown stage section "step"."#.to_string(),
+                    line: Some(6),
+                    column: Some(9),
+                },
+                JenkinsfileError {
+                    message: r#"Invalid agent type "docker" specified. Must be one of [any, label, none]"#
+                        .to_string(),
+                    line: Some(2),
+                    column: None,
+                },
+                JenkinsfileError {
+                    message: "Unexpected error".to_string(),
+                    line: None,
+                    column: None,
+                },
+            ]
+        );
+        assert_eq!(
+            errors_in_output("Jenkinsfile content 'node {}' did not contain the 'pipeline' step\n")
+                .unwrap(),
+            vec![JenkinsfileError {
+                message: "Jenkinsfile content 'node {}' did not contain the 'pipeline' step"
+                    .to_string(),
+                line: None,
+                column: None,
+            }]
+        );
+        match errors_in_output("<html><body>Please sign in</body></html>")
+            .unwrap_err()
+            .downcast_ref::<client::Error>()
+        {
+            Some(client::Error::UnexpectedResponse { .. }) => {}
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_convert_to_json() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("POST", "/pipeline-model-converter/toJson")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "jenkinsfile".to_string(),
+                "pipeline {}".to_string(),
+            ))
+            .with_body(
+                r#"{"status": "ok", "data": {"result": "failure", "errors": [
+                    {"line": 1, "column": 1, "message": "Missing required section \"stages\""}
+                ]}}"#,
+            )
+            .create();
+
+        let error = jenkins_client
+            .jenkinsfile_to_json("pipeline {}")
+            .unwrap_err();
+        match error.downcast_ref::<client::Error>() {
+            Some(client::Error::InvalidPipeline { errors }) => assert_eq!(
+                errors,
+                &vec![JenkinsfileError {
+                    message: "Missing required section \"stages\"".to_string(),
+                    line: Some(1),
+                    column: Some(1),
+                }]
+            ),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_convert_to_jenkinsfile() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("POST", "/pipeline-model-converter/toJenkinsfile")
+            .with_body(
+                r#"{"status": "ok", "data": {"result": "success",
+                    "jenkinsfile": "pipeline {\n  agent any\n}"}}"#,
+            )
+            .create();
+
+        let json = serde_json::json!({"pipeline": {"agent": {"type": "any"}}});
+        assert_eq!(
+            jenkins_client.json_to_jenkinsfile(&json).unwrap(),
+            "pipeline {\n  agent any\n}"
+        );
+    }
+}
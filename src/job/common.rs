@@ -4,6 +4,7 @@ use serde::{self, Deserialize, Serialize};
 
 use crate::helpers::Class;
 
+use super::patch::{patch_config, JobConfigPatch};
 use super::JobBuilder;
 use crate::action::CommonAction;
 use crate::build::{CommonBuild, ShortBuild};
@@ -177,6 +178,54 @@ pub trait Job {
         }
     }
 
+    /// Delete a `Job`, including when it's in a folder
+    fn delete(&self, jenkins_client: &Jenkins) -> Result<()> {
+        let path = jenkins_client.url_to_path(self.url());
+        match path.map_job(|name| Path::DeleteJob { name }) {
+            Some(path) => {
+                let _ = jenkins_client.post(&path)?;
+                Ok(())
+            }
+            None => Err(client::Error::InvalidUrl {
+                url: self.url().to_string(),
+                expected: client::error::ExpectedType::Job,
+            }
+            .into()),
+        }
+    }
+
+    /// Set the description of a `Job`
+    fn set_description(&self, jenkins_client: &Jenkins, description: &str) -> Result<()> {
+        let _ = jenkins_client.post_with_body(
+            &job_page(jenkins_client, self.url(), "submitDescription")?,
+            serde_urlencoded::to_string([("description", description)])?,
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Set the display name of a `Job`, or remove it with `None`
+    fn set_display_name(&self, jenkins_client: &Jenkins, display_name: Option<&str>) -> Result<()> {
+        self.patch_config(
+            jenkins_client,
+            &JobConfigPatch::new().with_display_name(display_name),
+        )
+    }
+
+    /// Apply a patch to the configuration of a `Job`. The detection of concurrent changes
+    /// is best effort: it fails with `ConfigConflict` if the configuration was changed
+    /// by someone else while the patch was applied, but a change made just before the
+    /// update is sent is still overwritten. It may need to be refreshed as it may have
+    /// been updated
+    fn patch_config(&self, jenkins_client: &Jenkins, patch: &JobConfigPatch) -> Result<()> {
+        patch_config(
+            jenkins_client,
+            &job_page(jenkins_client, self.url(), "config.xml")?,
+            self.name(),
+            patch,
+        )
+    }
+
     /// Add this job to the view `view_name`
     fn add_to_view<'a, V>(&self, jenkins_client: &Jenkins, view_name: V) -> Result<()>
     where
@@ -260,6 +309,24 @@ pub trait Job {
     }
 }
 
+/// Path to a page of a job, from its URL
+pub(crate) fn job_page<'a>(
+    jenkins_client: &Jenkins,
+    url: &'a str,
+    page: &'a str,
+) -> Result<Path<'a>> {
+    jenkins_client
+        .url_to_path(url)
+        .map_job(|name| Path::JobPage { name, page })
+        .ok_or_else(|| {
+            client::Error::InvalidUrl {
+                url: url.to_string(),
+                expected: client::error::ExpectedType::Job,
+            }
+            .into()
+        })
+}
+
 macro_rules! job_base_with_common_fields_and_impl {
     (
         $(#[$attr:meta])*
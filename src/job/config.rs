@@ -0,0 +1,1134 @@
+//! Typed models of the config.xml files of common job types. Elements that are not
+//! modeled are kept unchanged, and only the fields that were modified are written back,
+//! so a configuration that was not modified is serialized as it was parsed. Changing a
+//! field that can't be written back, like the class of a SCM other than git, fails with
+//! `UnsupportedConfigField` when serializing
+//!
+//! The models edit the parsed XML tree instead of being deserialized with serde, which
+//! would drop the elements that are not modeled and reformat the file
+
+use crate::client::{self, Result};
+use crate::xml::{self, Document, Element};
+use crate::Jenkins;
+
+const FREESTYLE_ROOT: &str = "project";
+const PIPELINE_ROOT: &str = "flow-definition";
+const MATRIX_ROOT: &str = "matrix-project";
+const FOLDER_ROOT: &str = "com.cloudbees.hudson.plugins.folder.Folder";
+const MULTIBRANCH_ROOT: &str =
+    "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject";
+
+const INLINE_DEFINITION: &str = "org.jenkinsci.plugins.workflow.cps.CpsFlowDefinition";
+const SCM_DEFINITION: &str = "org.jenkinsci.plugins.workflow.cps.CpsScmFlowDefinition";
+/// `_class` of the git SCM
+pub const GIT_SCM: &str = "hudson.plugins.git.GitSCM";
+const GIT_REMOTE: &str = "hudson.plugins.git.UserRemoteConfig";
+const GIT_BRANCH: &str = "hudson.plugins.git.BranchSpec";
+/// `_class` of a matrix axis with user defined values
+pub const TEXT_AXIS: &str = "hudson.matrix.TextAxis";
+/// `_class` of a matrix axis on agent labels
+pub const LABEL_AXIS: &str = "hudson.matrix.LabelAxis";
+
+fn child_text(element: &Element, name: &str) -> Option<String> {
+    element.child(name).map(Element::text)
+}
+
+fn child_bool(element: &Element, name: &str) -> bool {
+    child_text(element, name).as_deref() == Some("true")
+}
+
+/// Set the text of a child element, only if it changed to keep the formatting
+fn update_child_text(element: &mut Element, name: &str, text: &str) {
+    if child_text(element, name).as_deref() != Some(text) {
+        element.set_child_text(name, text);
+    }
+}
+
+/// Set the text of a child element, or remove it with `None`
+fn update_optional_child_text(element: &mut Element, name: &str, text: Option<&str>) {
+    match text {
+        Some(text) => update_child_text(element, name, text),
+        None => element.remove_child(name),
+    }
+}
+
+/// Fail if a field doesn't have the expected value once written, because this change is
+/// not supported by the model
+fn check_written(root: &Element, fields: &[(&'static str, bool)]) -> Result<()> {
+    match fields.iter().find(|(_, written)| !written) {
+        Some((field, _)) => Err(client::Error::UnsupportedConfigField {
+            field,
+            root: root.name.clone(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+fn element_with_class(name: &str, class: &str) -> Element {
+    let mut element = Element::new(name);
+    element.set_attribute("class", class);
+    element
+}
+
+/// Configuration of a freestyle project
+#[derive(Debug, Clone)]
+pub struct FreeStyleProjectConfig {
+    /// Description of the project
+    pub description: String,
+    /// Display name of the project, if different from its name
+    pub display_name: Option<String>,
+    /// Is the project disabled
+    pub disabled: bool,
+    /// Can builds run concurrently
+    pub concurrent_build: bool,
+    /// Label expression restricting where the project can run
+    pub assigned_node: Option<String>,
+    document: Document,
+}
+
+impl FreeStyleProjectConfig {
+    fn from_document(document: Document) -> Self {
+        let root = &document.root;
+        FreeStyleProjectConfig {
+            description: child_text(root, "description").unwrap_or_default(),
+            display_name: child_text(root, "displayName"),
+            disabled: child_bool(root, "disabled"),
+            concurrent_build: child_bool(root, "concurrentBuild"),
+            assigned_node: child_text(root, "assignedNode"),
+            document,
+        }
+    }
+
+    /// Serialize the configuration to a config.xml file. Fails with
+    /// `UnsupportedConfigField` if a field that can't be written back was changed
+    pub fn to_xml(&self) -> Result<String> {
+        let parsed = Self::from_document(self.document.clone());
+        let mut document = self.document.clone();
+        let root = &mut document.root;
+        if self.description != parsed.description {
+            update_child_text(root, "description", &self.description);
+        }
+        if self.display_name != parsed.display_name {
+            update_optional_child_text(root, "displayName", self.display_name.as_deref());
+        }
+        if self.disabled != parsed.disabled {
+            update_child_text(root, "disabled", &self.disabled.to_string());
+        }
+        if self.concurrent_build != parsed.concurrent_build {
+            update_child_text(root, "concurrentBuild", &self.concurrent_build.to_string());
+        }
+        if self.assigned_node != parsed.assigned_node {
+            write_assigned_node(root, self.assigned_node.as_deref());
+        }
+
+        let written = Self::from_document(document.clone());
+        check_written(
+            &document.root,
+            &[
+                ("description", written.description == self.description),
+                ("display_name", written.display_name == self.display_name),
+                ("disabled", written.disabled == self.disabled),
+                (
+                    "concurrent_build",
+                    written.concurrent_build == self.concurrent_build,
+                ),
+                ("assigned_node", written.assigned_node == self.assigned_node),
+            ],
+        )?;
+        Ok(document.to_string())
+    }
+}
+
+fn write_assigned_node(root: &mut Element, assigned_node: Option<&str>) {
+    update_optional_child_text(root, "assignedNode", assigned_node);
+    update_child_text(root, "canRoam", &assigned_node.is_none().to_string());
+}
+
+/// A SCM used to get a Jenkinsfile
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scm {
+    /// `_class` of the SCM, like [`GIT_SCM`](constant.GIT_SCM.html). Only git SCMs can be
+    /// changed, other SCMs are kept unchanged
+    pub class: String,
+    /// URL of the repository, for git
+    pub url: Option<String>,
+    /// Branches built, for git
+    pub branches: Vec<String>,
+}
+
+impl Scm {
+    /// Create a git SCM
+    pub fn git(url: &str, branches: &[&str]) -> Self {
+        Scm {
+            class: GIT_SCM.to_string(),
+            url: Some(url.to_string()),
+            branches: branches.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn from_element(scm: &Element) -> Self {
+        Scm {
+            class: scm.attribute("class").unwrap_or_default(),
+            url: scm
+                .child("userRemoteConfigs")
+                .and_then(|remotes| remotes.child(GIT_REMOTE))
+                .and_then(|remote| child_text(remote, "url")),
+            branches: git_branches(scm),
+        }
+    }
+
+    fn write(&self, scm: &mut Element) {
+        if self.class != GIT_SCM {
+            return;
+        }
+        if scm.child("configVersion").is_none() {
+            scm.push_child(Element::with_text("configVersion", "2"));
+        }
+        if let Some(ref url) = self.url {
+            let mut remotes = scm
+                .child("userRemoteConfigs")
+                .cloned()
+                .unwrap_or_else(|| Element::new("userRemoteConfigs"));
+            match remotes.child_mut(GIT_REMOTE) {
+                Some(remote) => update_child_text(remote, "url", url),
+                None => {
+                    let mut remote = Element::new(GIT_REMOTE);
+                    remote.push_child(Element::with_text("url", url));
+                    remotes.push_child(remote);
+                }
+            }
+            scm.replace_child(remotes);
+        }
+        if git_branches(scm) != self.branches {
+            let mut branches = Element::new("branches");
+            for branch in &self.branches {
+                let mut spec = Element::new(GIT_BRANCH);
+                spec.push_child(Element::with_text("name", branch));
+                branches.push_child(spec);
+            }
+            scm.replace_child(branches);
+        }
+    }
+}
+
+fn git_branches(scm: &Element) -> Vec<String> {
+    scm.child("branches")
+        .map(|branches| {
+            branches
+                .elements()
+                .filter_map(|spec| child_text(spec, "name"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Definition of a pipeline
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlowDefinition {
+    /// Inline script, with `CpsFlowDefinition`
+    Script {
+        /// Script of the pipeline
+        script: String,
+        /// Is the script run in the Groovy sandbox
+        sandbox: bool,
+    },
+    /// Jenkinsfile read from a SCM, with `CpsScmFlowDefinition`
+    Scm {
+        /// SCM containing the Jenkinsfile
+        scm: Scm,
+        /// Path to the Jenkinsfile in the SCM
+        script_path: String,
+        /// Read the Jenkinsfile without a full checkout
+        lightweight: bool,
+    },
+    /// Another definition, kept unchanged. It can't be set on a pipeline with a
+    /// different definition
+    Other {
+        /// `_class` of the definition
+        class: String,
+    },
+}
+
+impl FlowDefinition {
+    fn from_element(definition: Option<&Element>) -> Self {
+        let class = definition
+            .and_then(|definition| definition.attribute("class"))
+            .unwrap_or_default();
+        match definition {
+            Some(definition) if class == INLINE_DEFINITION => FlowDefinition::Script {
+                script: child_text(definition, "script").unwrap_or_default(),
+                sandbox: child_bool(definition, "sandbox"),
+            },
+            Some(definition) if class == SCM_DEFINITION => FlowDefinition::Scm {
+                scm: definition
+                    .child("scm")
+                    .map(Scm::from_element)
+                    .unwrap_or_else(|| Scm {
+                        class: String::new(),
+                        url: None,
+                        branches: vec![],
+                    }),
+                script_path: child_text(definition, "scriptPath").unwrap_or_default(),
+                lightweight: child_bool(definition, "lightweight"),
+            },
+            _ => FlowDefinition::Other { class },
+        }
+    }
+
+    /// Write the definition, only changing the fields that differ from the `parsed`
+    /// definition when it has the same type
+    fn write(&self, root: &mut Element, parsed: &FlowDefinition) {
+        let existing = |class: &str| match root.child("definition") {
+            Some(definition) if definition.attribute("class").as_deref() == Some(class) => {
+                definition.clone()
+            }
+            _ => element_with_class("definition", class),
+        };
+        let definition = match self {
+            FlowDefinition::Script { script, sandbox } => {
+                let mut definition = existing(INLINE_DEFINITION);
+                let (parsed_script, parsed_sandbox) = match parsed {
+                    FlowDefinition::Script { script, sandbox } => (Some(script), Some(sandbox)),
+                    _ => (None, None),
+                };
+                if parsed_script != Some(script) {
+                    update_child_text(&mut definition, "script", script);
+                }
+                if parsed_sandbox != Some(sandbox) {
+                    update_child_text(&mut definition, "sandbox", &sandbox.to_string());
+                }
+                definition
+            }
+            FlowDefinition::Scm {
+                scm,
+                script_path,
+                lightweight,
+            } => {
+                let mut definition = existing(SCM_DEFINITION);
+                let (parsed_scm, parsed_script_path, parsed_lightweight) = match parsed {
+                    FlowDefinition::Scm {
+                        scm,
+                        script_path,
+                        lightweight,
+                    } => (Some(scm), Some(script_path), Some(lightweight)),
+                    _ => (None, None, None),
+                };
+                if parsed_scm != Some(scm) {
+                    let mut scm_element = match definition.child("scm") {
+                        Some(element) if element.attribute("class") == Some(scm.class.clone()) => {
+                            element.clone()
+                        }
+                        _ => element_with_class("scm", &scm.class),
+                    };
+                    scm.write(&mut scm_element);
+                    if definition.child("scm") != Some(&scm_element) {
+                        definition.replace_child(scm_element);
+                    }
+                }
+                if parsed_script_path != Some(script_path) {
+                    update_child_text(&mut definition, "scriptPath", script_path);
+                }
+                if parsed_lightweight != Some(lightweight) {
+                    update_child_text(&mut definition, "lightweight", &lightweight.to_string());
+                }
+                definition
+            }
+            FlowDefinition::Other { .. } => return,
+        };
+        if root.child("definition") != Some(&definition) {
+            root.replace_child(definition);
+        }
+    }
+}
+
+/// Configuration of a pipeline
+#[derive(Debug, Clone)]
+pub struct WorkflowJobConfig {
+    /// Description of the pipeline
+    pub description: String,
+    /// Display name of the pipeline, if different from its name
+    pub display_name: Option<String>,
+    /// Is the pipeline disabled
+    pub disabled: bool,
+    /// Definition of the pipeline
+    pub definition: FlowDefinition,
+    document: Document,
+}
+
+impl WorkflowJobConfig {
+    fn from_document(document: Document) -> Self {
+        let root = &document.root;
+        WorkflowJobConfig {
+            description: child_text(root, "description").unwrap_or_default(),
+            display_name: child_text(root, "displayName"),
+            disabled: child_bool(root, "disabled"),
+            definition: FlowDefinition::from_element(root.child("definition")),
+            document,
+        }
+    }
+
+    /// Serialize the configuration to a config.xml file. Fails with
+    /// `UnsupportedConfigField` if a field that can't be written back was changed
+    pub fn to_xml(&self) -> Result<String> {
+        let parsed = Self::from_document(self.document.clone());
+        let mut document = self.document.clone();
+        let root = &mut document.root;
+        if self.description != parsed.description {
+            update_child_text(root, "description", &self.description);
+        }
+        if self.display_name != parsed.display_name {
+            update_optional_child_text(root, "displayName", self.display_name.as_deref());
+        }
+        if self.disabled != parsed.disabled {
+            update_child_text(root, "disabled", &self.disabled.to_string());
+        }
+        if self.definition != parsed.definition {
+            self.definition.write(root, &parsed.definition);
+        }
+
+        let written = Self::from_document(document.clone());
+        check_written(
+            &document.root,
+            &[
+                ("description", written.description == self.description),
+                ("display_name", written.display_name == self.display_name),
+                ("disabled", written.disabled == self.disabled),
+                ("definition", written.definition == self.definition),
+            ],
+        )?;
+        Ok(document.to_string())
+    }
+}
+
+/// An axis of a matrix project
+#[derive(Debug, Clone, PartialEq)]
+pub struct Axis {
+    /// `_class` of the axis, like [`TEXT_AXIS`](constant.TEXT_AXIS.html) or
+    /// [`LABEL_AXIS`](constant.LABEL_AXIS.html)
+    pub class: String,
+    /// Name of the axis
+    pub name: String,
+    /// Values of the axis
+    pub values: Vec<String>,
+}
+
+impl Axis {
+    /// Create an axis with user defined values
+    pub fn text(name: &str, values: &[&str]) -> Self {
+        Axis {
+            class: TEXT_AXIS.to_string(),
+            name: name.to_string(),
+            values: values.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Create an axis on agent labels
+    pub fn label(name: &str, labels: &[&str]) -> Self {
+        Axis {
+            class: LABEL_AXIS.to_string(),
+            ..Axis::text(name, labels)
+        }
+    }
+
+    fn from_element(axis: &Element) -> Self {
+        Axis {
+            class: axis.name.clone(),
+            name: child_text(axis, "name").unwrap_or_default(),
+            values: axis
+                .child("values")
+                .map(|values| values.elements().map(Element::text).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn to_element(&self, existing: Option<&Element>) -> Element {
+        let mut axis = existing
+            .cloned()
+            .unwrap_or_else(|| Element::new(&self.class));
+        update_child_text(&mut axis, "name", &self.name);
+        if Axis::from_element(&axis).values != self.values {
+            let mut values = Element::new("values");
+            for value in &self.values {
+                values.push_child(Element::with_text("string", value));
+            }
+            axis.replace_child(values);
+        }
+        axis
+    }
+}
+
+/// Configuration of a matrix project
+#[derive(Debug, Clone)]
+pub struct MatrixProjectConfig {
+    /// Description of the project
+    pub description: String,
+    /// Display name of the project, if different from its name
+    pub display_name: Option<String>,
+    /// Is the project disabled
+    pub disabled: bool,
+    /// Can builds run concurrently
+    pub concurrent_build: bool,
+    /// Label expression restricting where the parent build can run
+    pub assigned_node: Option<String>,
+    /// Axes of the matrix
+    pub axes: Vec<Axis>,
+    document: Document,
+}
+
+impl MatrixProjectConfig {
+    fn from_document(document: Document) -> Self {
+        let root = &document.root;
+        MatrixProjectConfig {
+            description: child_text(root, "description").unwrap_or_default(),
+            display_name: child_text(root, "displayName"),
+            disabled: child_bool(root, "disabled"),
+            concurrent_build: child_bool(root, "concurrentBuild"),
+            assigned_node: child_text(root, "assignedNode"),
+            axes: root
+                .child("axes")
+                .map(|axes| axes.elements().map(Axis::from_element).collect())
+                .unwrap_or_default(),
+            document,
+        }
+    }
+
+    /// Serialize the configuration to a config.xml file. Fails with
+    /// `UnsupportedConfigField` if a field that can't be written back was changed
+    pub fn to_xml(&self) -> Result<String> {
+        let parsed = Self::from_document(self.document.clone());
+        let mut document = self.document.clone();
+        let root = &mut document.root;
+        if self.description != parsed.description {
+            update_child_text(root, "description", &self.description);
+        }
+        if self.display_name != parsed.display_name {
+            update_optional_child_text(root, "displayName", self.display_name.as_deref());
+        }
+        if self.disabled != parsed.disabled {
+            update_child_text(root, "disabled", &self.disabled.to_string());
+        }
+        if self.concurrent_build != parsed.concurrent_build {
+            update_child_text(root, "concurrentBuild", &self.concurrent_build.to_string());
+        }
+        if self.assigned_node != parsed.assigned_node {
+            write_assigned_node(root, self.assigned_node.as_deref());
+        }
+
+        let existing = root.child("axes").cloned();
+        if self.axes != parsed.axes {
+            let mut axes = Element::new("axes");
+            for axis in &self.axes {
+                let previous = existing.as_ref().and_then(|existing| {
+                    existing.elements().find(|element| {
+                        element.name == axis.class
+                            && child_text(element, "name").as_deref() == Some(&axis.name)
+                    })
+                });
+                axes.push_child(axis.to_element(previous));
+            }
+            root.replace_child(axes);
+        }
+
+        let written = Self::from_document(document.clone());
+        check_written(
+            &document.root,
+            &[
+                ("description", written.description == self.description),
+                ("display_name", written.display_name == self.display_name),
+                ("disabled", written.disabled == self.disabled),
+                (
+                    "concurrent_build",
+                    written.concurrent_build == self.concurrent_build,
+                ),
+                ("assigned_node", written.assigned_node == self.assigned_node),
+                ("axes", written.axes == self.axes),
+            ],
+        )?;
+        Ok(document.to_string())
+    }
+}
+
+/// Configuration of a folder
+#[derive(Debug, Clone)]
+pub struct FolderConfig {
+    /// Description of the folder
+    pub description: String,
+    /// Display name of the folder, if different from its name
+    pub display_name: Option<String>,
+    document: Document,
+}
+
+impl FolderConfig {
+    fn from_document(document: Document) -> Self {
+        let root = &document.root;
+        FolderConfig {
+            description: child_text(root, "description").unwrap_or_default(),
+            display_name: child_text(root, "displayName"),
+            document,
+        }
+    }
+
+    /// Serialize the configuration to a config.xml file. Fails with
+    /// `UnsupportedConfigField` if a field that can't be written back was changed
+    pub fn to_xml(&self) -> Result<String> {
+        let parsed = Self::from_document(self.document.clone());
+        let mut document = self.document.clone();
+        let root = &mut document.root;
+        if self.description != parsed.description {
+            update_child_text(root, "description", &self.description);
+        }
+        if self.display_name != parsed.display_name {
+            update_optional_child_text(root, "displayName", self.display_name.as_deref());
+        }
+
+        let written = Self::from_document(document.clone());
+        check_written(
+            &document.root,
+            &[
+                ("description", written.description == self.description),
+                ("display_name", written.display_name == self.display_name),
+            ],
+        )?;
+        Ok(document.to_string())
+    }
+}
+
+/// A branch source of a multibranch project
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchSource {
+    /// `_class` of the source
+    pub class: String,
+    /// ID of the source
+    pub id: Option<String>,
+    /// URL of the repository, for git sources. This is the only field that can be
+    /// changed, changing the other fields or the list of sources is not supported
+    pub remote: Option<String>,
+    /// Owner of the repository, for GitHub and Bitbucket sources
+    pub repo_owner: Option<String>,
+    /// Name of the repository, for GitHub and Bitbucket sources
+    pub repository: Option<String>,
+}
+
+impl BranchSource {
+    fn from_element(source: &Element) -> Self {
+        BranchSource {
+            class: source.attribute("class").unwrap_or_default(),
+            id: child_text(source, "id"),
+            remote: child_text(source, "remote"),
+            repo_owner: child_text(source, "repoOwner"),
+            repository: child_text(source, "repository"),
+        }
+    }
+}
+
+/// Configuration of a multibranch pipeline project
+#[derive(Debug, Clone)]
+pub struct WorkflowMultiBranchProjectConfig {
+    /// Description of the project
+    pub description: String,
+    /// Display name of the project, if different from its name
+    pub display_name: Option<String>,
+    /// Path to the Jenkinsfile in the branches, only when the project has a branch
+    /// project factory reading it
+    pub script_path: Option<String>,
+    /// Branch sources of the project
+    pub sources: Vec<BranchSource>,
+    document: Document,
+}
+
+fn branch_sources(root: &mut Element) -> Vec<&mut Element> {
+    root.child_mut("sources")
+        .and_then(|sources| sources.child_mut("data"))
+        .map(|data| {
+            data.children
+                .iter_mut()
+                .filter_map(|node| match node {
+                    xml::Node::Element(source) => source.child_mut("source"),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl WorkflowMultiBranchProjectConfig {
+    fn from_document(mut document: Document) -> Self {
+        let sources = branch_sources(&mut document.root)
+            .into_iter()
+            .map(|source| BranchSource::from_element(source))
+            .collect();
+        let root = &document.root;
+        WorkflowMultiBranchProjectConfig {
+            description: child_text(root, "description").unwrap_or_default(),
+            display_name: child_text(root, "displayName"),
+            script_path: root
+                .child("factory")
+                .and_then(|factory| child_text(factory, "scriptPath")),
+            sources,
+            document,
+        }
+    }
+
+    /// Serialize the configuration to a config.xml file. Fails with
+    /// `UnsupportedConfigField` if a field that can't be written back was changed
+    pub fn to_xml(&self) -> Result<String> {
+        let parsed = Self::from_document(self.document.clone());
+        let mut document = self.document.clone();
+        let root = &mut document.root;
+        if self.description != parsed.description {
+            update_child_text(root, "description", &self.description);
+        }
+        if self.display_name != parsed.display_name {
+            update_optional_child_text(root, "displayName", self.display_name.as_deref());
+        }
+        if let (Some(factory), Some(script_path)) =
+            (root.child_mut("factory"), self.script_path.as_ref())
+        {
+            if self.script_path != parsed.script_path {
+                update_child_text(factory, "scriptPath", script_path);
+            }
+        }
+        for ((element, source), parsed_source) in branch_sources(root)
+            .into_iter()
+            .zip(&self.sources)
+            .zip(&parsed.sources)
+        {
+            if let Some(ref remote) = source.remote {
+                if source.remote != parsed_source.remote {
+                    update_child_text(element, "remote", remote);
+                }
+            }
+        }
+
+        let written = Self::from_document(document.clone());
+        check_written(
+            &document.root,
+            &[
+                ("description", written.description == self.description),
+                ("display_name", written.display_name == self.display_name),
+                ("script_path", written.script_path == self.script_path),
+                ("sources", written.sources == self.sources),
+            ],
+        )?;
+        Ok(document.to_string())
+    }
+}
+
+/// Typed configuration of a job, from its config.xml file
+#[derive(Debug, Clone)]
+pub enum JobConfig {
+    /// A freestyle project
+    FreeStyleProject(FreeStyleProjectConfig),
+    /// A pipeline
+    WorkflowJob(WorkflowJobConfig),
+    /// A matrix project
+    MatrixProject(MatrixProjectConfig),
+    /// A folder
+    Folder(FolderConfig),
+    /// A multibranch pipeline project
+    WorkflowMultiBranchProject(WorkflowMultiBranchProjectConfig),
+    /// Another type of job, with its config.xml file
+    Other(String),
+}
+
+impl JobConfig {
+    /// Parse a config.xml file
+    pub fn parse(config: &str) -> Result<Self> {
+        let document = Document::parse(config)?;
+        Ok(match document.root.name.as_str() {
+            FREESTYLE_ROOT => {
+                JobConfig::FreeStyleProject(FreeStyleProjectConfig::from_document(document))
+            }
+            PIPELINE_ROOT => JobConfig::WorkflowJob(WorkflowJobConfig::from_document(document)),
+            MATRIX_ROOT => JobConfig::MatrixProject(MatrixProjectConfig::from_document(document)),
+            FOLDER_ROOT => JobConfig::Folder(FolderConfig::from_document(document)),
+            MULTIBRANCH_ROOT => JobConfig::WorkflowMultiBranchProject(
+                WorkflowMultiBranchProjectConfig::from_document(document),
+            ),
+            _ => JobConfig::Other(config.to_string()),
+        })
+    }
+
+    /// Serialize the configuration to a config.xml file. Fails with
+    /// `UnsupportedConfigField` if a field that can't be written back was changed
+    pub fn to_xml(&self) -> Result<String> {
+        match self {
+            JobConfig::FreeStyleProject(config) => config.to_xml(),
+            JobConfig::WorkflowJob(config) => config.to_xml(),
+            JobConfig::MatrixProject(config) => config.to_xml(),
+            JobConfig::Folder(config) => config.to_xml(),
+            JobConfig::WorkflowMultiBranchProject(config) => config.to_xml(),
+            JobConfig::Other(config) => Ok(config.clone()),
+        }
+    }
+}
+
+impl Jenkins {
+    /// Get the typed configuration of a job, from its full name with the names of the
+    /// folders containing it separated by `/`
+    pub fn get_job_config(&self, full_name: &str) -> Result<JobConfig> {
+        JobConfig::parse(&self.get_job_config_xml(full_name)?)
+    }
+
+    /// Update the configuration of a job, from its full name with the names of the
+    /// folders containing it separated by `/`
+    pub fn update_job_config(&self, full_name: &str, config: &JobConfig) -> Result<()> {
+        self.update_job_config_xml(full_name, &config.to_xml()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PIPELINE: &str = r#"<?xml version='1.1' encoding='UTF-8'?>
+<flow-definition plugin="workflow-job@2.40">
+  <actions/>
+  <description>deploys &amp; tests</description>
+  <keepDependencies>false</keepDependencies>
+  <properties>
+    <com.example.UnknownProperty plugin="example@1.0">
+      <value>kept</value>
+    </com.example.UnknownProperty>
+  </properties>
+  <definition class="org.jenkinsci.plugins.workflow.cps.CpsScmFlowDefinition" plugin="workflow-cps@2.87">
+    <scm class="hudson.plugins.git.GitSCM" plugin="git@4.5.0">
+      <configVersion>2</configVersion>
+      <userRemoteConfigs>
+        <hudson.plugins.git.UserRemoteConfig>
+          <url>https://github.com/org/repo.git</url>
+        </hudson.plugins.git.UserRemoteConfig>
+      </userRemoteConfigs>
+      <branches>
+        <hudson.plugins.git.BranchSpec>
+          <name>*/main</name>
+        </hudson.plugins.git.BranchSpec>
+      </branches>
+      <extensions/>
+    </scm>
+    <scriptPath>Jenkinsfile</scriptPath>
+    <lightweight>true</lightweight>
+  </definition>
+  <triggers/>
+  <disabled>false</disabled>
+</flow-definition>"#;
+
+    static MATRIX: &str = r#"<?xml version='1.1' encoding='UTF-8'?>
+<matrix-project plugin="matrix-project@1.18">
+  <description></description>
+  <canRoam>true</canRoam>
+  <disabled>false</disabled>
+  <concurrentBuild>false</concurrentBuild>
+  <axes>
+    <hudson.matrix.TextAxis>
+      <name>os</name>
+      <values>
+        <string>linux</string>
+        <string>windows</string>
+      </values>
+    </hudson.matrix.TextAxis>
+  </axes>
+  <builders/>
+</matrix-project>"#;
+
+    #[test]
+    fn can_round_trip_unchanged() {
+        assert_eq!(
+            JobConfig::parse(PIPELINE).unwrap().to_xml().unwrap(),
+            PIPELINE
+        );
+        assert_eq!(JobConfig::parse(MATRIX).unwrap().to_xml().unwrap(), MATRIX);
+    }
+
+    #[test]
+    fn can_round_trip_minimal_configs_unchanged() {
+        for config in &[
+            "<project><description>x</description></project>",
+            "<project/>",
+            "<flow-definition><definition class=\"org.jenkinsci.plugins.workflow.cps.CpsFlowDefinition\"/></flow-definition>",
+            "<matrix-project/>",
+            "<com.cloudbees.hudson.plugins.folder.Folder/>",
+            "<org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject><factory/></org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject>",
+        ] {
+            assert_eq!(&JobConfig::parse(config).unwrap().to_xml().unwrap(), config);
+        }
+    }
+
+    #[test]
+    fn can_edit_pipeline() {
+        let mut config = match JobConfig::parse(PIPELINE).unwrap() {
+            JobConfig::WorkflowJob(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        assert_eq!(config.description, "deploys & tests");
+        match config.definition {
+            FlowDefinition::Scm {
+                ref scm,
+                ref mut script_path,
+                ..
+            } => {
+                assert_eq!(
+                    scm,
+                    &Scm::git("https://github.com/org/repo.git", &["*/main"])
+                );
+                *script_path = "ci/Jenkinsfile".to_string();
+            }
+            ref other => panic!("unexpected definition {:?}", other),
+        }
+
+        let xml = config.to_xml().unwrap();
+        assert_eq!(
+            xml,
+            PIPELINE.replace(
+                "<scriptPath>Jenkinsfile</scriptPath>",
+                "<scriptPath>ci/Jenkinsfile</scriptPath>"
+            )
+        );
+
+        config.definition = FlowDefinition::Script {
+            script: "node { echo 'hello' }".to_string(),
+            sandbox: true,
+        };
+        let xml = config.to_xml().unwrap();
+        assert!(xml.contains("<script>node { echo &apos;hello&apos; }</script>"));
+        assert!(xml.contains("<value>kept</value>"));
+        assert!(!xml.contains("scriptPath"));
+    }
+
+    #[test]
+    fn can_edit_matrix_axes() {
+        let mut config = match JobConfig::parse(MATRIX).unwrap() {
+            JobConfig::MatrixProject(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        assert_eq!(config.axes, vec![Axis::text("os", &["linux", "windows"])]);
+
+        config.axes[0].values.push("macos".to_string());
+        config.axes.push(Axis::label("agent", &["docker"]));
+        config.assigned_node = Some("master".to_string());
+
+        let edited = match JobConfig::parse(&config.to_xml().unwrap()).unwrap() {
+            JobConfig::MatrixProject(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        assert_eq!(edited.axes, config.axes);
+        assert_eq!(edited.assigned_node, Some("master".to_string()));
+    }
+
+    #[test]
+    fn can_edit_multibranch_script_path() {
+        let config = r#"<org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject>
+  <description/>
+  <sources class="jenkins.branch.MultiBranchProject$BranchSourceList">
+    <data>
+      <jenkins.branch.BranchSource>
+        <source class="jenkins.plugins.git.GitSCMSource">
+          <id>1</id>
+          <remote>https://github.com/org/repo.git</remote>
+        </source>
+      </jenkins.branch.BranchSource>
+    </data>
+  </sources>
+  <factory class="org.jenkinsci.plugins.workflow.multibranch.WorkflowBranchProjectFactory">
+    <scriptPath>Jenkinsfile</scriptPath>
+  </factory>
+</org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject>"#;
+        let mut parsed = match JobConfig::parse(config).unwrap() {
+            JobConfig::WorkflowMultiBranchProject(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        assert_eq!(
+            parsed.sources[0].remote,
+            Some("https://github.com/org/repo.git".to_string())
+        );
+        parsed.script_path = Some("ci/Jenkinsfile".to_string());
+        parsed.sources[0].remote = Some("https://github.com/org/moved.git".to_string());
+        assert_eq!(
+            parsed.to_xml().unwrap(),
+            config
+                .replace("<scriptPath>Jenkinsfile", "<scriptPath>ci/Jenkinsfile")
+                .replace("org/repo.git", "org/moved.git")
+        );
+    }
+
+    static MULTIBRANCH: &str = r#"<org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject>
+  <description/>
+  <sources class="jenkins.branch.MultiBranchProject$BranchSourceList">
+    <data>
+      <jenkins.branch.BranchSource>
+        <source class="org.jenkinsci.plugins.github_branch_source.GitHubSCMSource">
+          <id>1</id>
+          <repoOwner>org</repoOwner>
+          <repository>repo</repository>
+        </source>
+      </jenkins.branch.BranchSource>
+    </data>
+  </sources>
+</org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject>"#;
+
+    fn unsupported_field(config: &JobConfig) -> &'static str {
+        match config.to_xml().unwrap_err().downcast_ref::<client::Error>() {
+            Some(client::Error::UnsupportedConfigField { field, .. }) => field,
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_round_trip_all_fields() {
+        let mut freestyle =
+            match JobConfig::parse("<project><description>old</description></project>").unwrap() {
+                JobConfig::FreeStyleProject(config) => config,
+                other => panic!("unexpected config {:?}", other),
+            };
+        freestyle.description = "new".to_string();
+        freestyle.display_name = Some("New".to_string());
+        freestyle.disabled = true;
+        freestyle.concurrent_build = true;
+        freestyle.assigned_node = Some("linux".to_string());
+        match JobConfig::parse(&freestyle.to_xml().unwrap()).unwrap() {
+            JobConfig::FreeStyleProject(written) => {
+                assert_eq!(written.description, freestyle.description);
+                assert_eq!(written.display_name, freestyle.display_name);
+                assert_eq!(written.disabled, freestyle.disabled);
+                assert_eq!(written.concurrent_build, freestyle.concurrent_build);
+                assert_eq!(written.assigned_node, freestyle.assigned_node);
+            }
+            other => panic!("unexpected config {:?}", other),
+        }
+
+        let mut pipeline = match JobConfig::parse(PIPELINE).unwrap() {
+            JobConfig::WorkflowJob(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        pipeline.description = "new".to_string();
+        pipeline.display_name = Some("New".to_string());
+        pipeline.disabled = true;
+        pipeline.definition = FlowDefinition::Scm {
+            scm: Scm::git("https://github.com/org/other.git", &["*/dev", "*/main"]),
+            script_path: "ci/Jenkinsfile".to_string(),
+            lightweight: false,
+        };
+        match JobConfig::parse(&pipeline.to_xml().unwrap()).unwrap() {
+            JobConfig::WorkflowJob(written) => {
+                assert_eq!(written.description, pipeline.description);
+                assert_eq!(written.display_name, pipeline.display_name);
+                assert_eq!(written.disabled, pipeline.disabled);
+                assert_eq!(written.definition, pipeline.definition);
+            }
+            other => panic!("unexpected config {:?}", other),
+        }
+
+        let mut matrix = match JobConfig::parse(MATRIX).unwrap() {
+            JobConfig::MatrixProject(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        matrix.description = "new".to_string();
+        matrix.display_name = Some("New".to_string());
+        matrix.disabled = true;
+        matrix.concurrent_build = true;
+        matrix.assigned_node = Some("linux".to_string());
+        matrix.axes = vec![Axis::label("agent", &["docker"])];
+        match JobConfig::parse(&matrix.to_xml().unwrap()).unwrap() {
+            JobConfig::MatrixProject(written) => {
+                assert_eq!(written.description, matrix.description);
+                assert_eq!(written.display_name, matrix.display_name);
+                assert_eq!(written.disabled, matrix.disabled);
+                assert_eq!(written.concurrent_build, matrix.concurrent_build);
+                assert_eq!(written.assigned_node, matrix.assigned_node);
+                assert_eq!(written.axes, matrix.axes);
+            }
+            other => panic!("unexpected config {:?}", other),
+        }
+
+        let mut folder = match JobConfig::parse(&format!("<{}/>", FOLDER_ROOT)).unwrap() {
+            JobConfig::Folder(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        folder.description = "new".to_string();
+        folder.display_name = Some("New".to_string());
+        match JobConfig::parse(&folder.to_xml().unwrap()).unwrap() {
+            JobConfig::Folder(written) => {
+                assert_eq!(written.description, folder.description);
+                assert_eq!(written.display_name, folder.display_name);
+            }
+            other => panic!("unexpected config {:?}", other),
+        }
+
+        let mut multibranch = match JobConfig::parse(MULTIBRANCH).unwrap() {
+            JobConfig::WorkflowMultiBranchProject(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        multibranch.description = "new".to_string();
+        multibranch.display_name = Some("New".to_string());
+        multibranch.sources[0].remote = Some("https://github.com/org/repo.git".to_string());
+        match JobConfig::parse(&multibranch.to_xml().unwrap()).unwrap() {
+            JobConfig::WorkflowMultiBranchProject(written) => {
+                assert_eq!(written.description, multibranch.description);
+                assert_eq!(written.display_name, multibranch.display_name);
+                assert_eq!(written.sources, multibranch.sources);
+            }
+            other => panic!("unexpected config {:?}", other),
+        }
+    }
+
+    #[test]
+    fn fails_on_fields_not_written() {
+        let multibranch = match JobConfig::parse(MULTIBRANCH).unwrap() {
+            JobConfig::WorkflowMultiBranchProject(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        let edits: Vec<fn(&mut WorkflowMultiBranchProjectConfig)> = vec![
+            |config| config.sources[0].id = Some("2".to_string()),
+            |config| config.sources[0].class = "jenkins.plugins.git.GitSCMSource".to_string(),
+            |config| config.sources[0].repo_owner = Some("other".to_string()),
+            |config| config.sources[0].repository = None,
+            |config| {
+                let source = config.sources[0].clone();
+                config.sources.push(source)
+            },
+            |config| config.sources.clear(),
+        ];
+        for edit in edits {
+            let mut config = multibranch.clone();
+            edit(&mut config);
+            assert_eq!(
+                unsupported_field(&JobConfig::WorkflowMultiBranchProject(config)),
+                "sources"
+            );
+        }
+        let mut config = multibranch;
+        config.script_path = Some("Jenkinsfile".to_string());
+        assert_eq!(
+            unsupported_field(&JobConfig::WorkflowMultiBranchProject(config)),
+            "script_path"
+        );
+
+        let pipeline = match JobConfig::parse(PIPELINE).unwrap() {
+            JobConfig::WorkflowJob(config) => config,
+            other => panic!("unexpected config {:?}", other),
+        };
+        let mut config = pipeline.clone();
+        config.definition = FlowDefinition::Other {
+            class: "com.example.FlowDefinition".to_string(),
+        };
+        assert_eq!(
+            unsupported_field(&JobConfig::WorkflowJob(config)),
+            "definition"
+        );
+        let mut config = pipeline;
+        config.definition = FlowDefinition::Scm {
+            scm: Scm {
+                class: "hudson.scm.SubversionSCM".to_string(),
+                url: Some("https://svn.example.com/repo".to_string()),
+                branches: vec![],
+            },
+            script_path: "Jenkinsfile".to_string(),
+            lightweight: true,
+        };
+        assert_eq!(
+            unsupported_field(&JobConfig::WorkflowJob(config)),
+            "definition"
+        );
+    }
+}
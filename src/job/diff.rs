@@ -0,0 +1,348 @@
+//! Compare configurations of jobs, and detect jobs whose configuration drifted
+
+use std::collections::{BTreeSet, HashMap};
+
+use reqwest::StatusCode;
+
+use crate::client::Result;
+use crate::client_internals::status_of;
+use crate::xml::{Document, Element, Node};
+use crate::Jenkins;
+
+/// Attributes ignored when comparing configurations, as they change with plugin updates
+const IGNORED_ATTRIBUTES: [&str; 1] = ["plugin"];
+
+/// Kind of change between two configurations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    /// Present only in the second configuration
+    Added,
+    /// Present only in the first configuration
+    Removed,
+    /// Present in both configurations with different values
+    Modified,
+}
+
+/// A change between two configurations
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// Path to the element that changed, like `/project/disabled`. Elements repeated
+    /// under the same parent are indexed, like `/project/builders/hudson.tasks.Shell[1]`,
+    /// and attributes are prefixed with `@`, like `/flow-definition/definition/@class`
+    pub path: String,
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// Value in the first configuration, the text of an element or an attribute, or the
+    /// normalized XML of an element that was removed
+    pub before: Option<String>,
+    /// Value in the second configuration, the text of an element or an attribute, or
+    /// the normalized XML of an element that was added
+    pub after: Option<String>,
+}
+
+/// Normalize an element: comments, processing instructions and whitespace between
+/// elements are removed, text is trimmed, attributes are sorted and ignored attributes
+/// like plugin versions are removed
+fn normalize(element: &Element) -> Element {
+    let mut attributes = element
+        .attributes
+        .iter()
+        .filter(|(key, _)| !IGNORED_ATTRIBUTES.contains(&key.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    attributes.sort();
+
+    let mut normalized = Element {
+        name: element.name.clone(),
+        attributes,
+        children: element
+            .elements()
+            .map(|child| Node::Element(normalize(child)))
+            .collect(),
+    };
+    if normalized.children.is_empty() {
+        normalized.set_text(element.text().trim());
+    }
+    normalized
+}
+
+/// Child elements, with their index among the siblings with the same name
+fn indexed_children(element: &Element) -> Vec<(&str, usize, &Element)> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    element
+        .elements()
+        .map(|child| {
+            let index = seen.entry(child.name.as_str()).or_insert(0);
+            *index += 1;
+            (child.name.as_str(), *index, child)
+        })
+        .collect()
+}
+
+fn diff_elements(path: &str, before: &Element, after: &Element, changes: &mut Vec<ConfigChange>) {
+    let keys = before
+        .attributes
+        .iter()
+        .chain(after.attributes.iter())
+        .map(|(key, _)| key.as_str())
+        .collect::<BTreeSet<_>>();
+    for key in keys {
+        let (before, after) = (before.attribute(key), after.attribute(key));
+        if before != after {
+            changes.push(ConfigChange {
+                path: format!("{}/@{}", path, key),
+                kind: change_kind(&before, &after),
+                before,
+                after,
+            });
+        }
+    }
+
+    // normalized elements contain either child elements or text
+    let (before_text, after_text) = (before.text(), after.text());
+    if before_text != after_text {
+        changes.push(ConfigChange {
+            path: path.to_string(),
+            kind: ChangeKind::Modified,
+            before: Some(before_text),
+            after: Some(after_text),
+        });
+    }
+
+    let before_children = indexed_children(before);
+    let after_children = indexed_children(after);
+    let child_path = |name: &str, index: usize| {
+        let repeated = before_children
+            .iter()
+            .chain(after_children.iter())
+            .any(|(other, other_index, _)| *other == name && *other_index > 1);
+        if repeated {
+            format!("{}/{}[{}]", path, name, index)
+        } else {
+            format!("{}/{}", path, name)
+        }
+    };
+    for (name, index, before_child) in &before_children {
+        match after_children
+            .iter()
+            .find(|(other, other_index, _)| other == name && other_index == index)
+        {
+            Some((_, _, after_child)) => diff_elements(
+                &child_path(name, *index),
+                before_child,
+                after_child,
+                changes,
+            ),
+            None => changes.push(ConfigChange {
+                path: child_path(name, *index),
+                kind: ChangeKind::Removed,
+                before: Some(before_child.to_string()),
+                after: None,
+            }),
+        }
+    }
+    for (name, index, after_child) in &after_children {
+        if !before_children
+            .iter()
+            .any(|(other, other_index, _)| other == name && other_index == index)
+        {
+            changes.push(ConfigChange {
+                path: child_path(name, *index),
+                kind: ChangeKind::Added,
+                before: None,
+                after: Some(after_child.to_string()),
+            });
+        }
+    }
+}
+
+fn change_kind(before: &Option<String>, after: &Option<String>) -> ChangeKind {
+    match (before, after) {
+        (None, Some(_)) => ChangeKind::Added,
+        (Some(_), None) => ChangeKind::Removed,
+        _ => ChangeKind::Modified,
+    }
+}
+
+/// Compare two config.xml files. Differences in whitespace, comments, attribute order
+/// and plugin versions are ignored
+pub fn config_diff(before: &str, after: &str) -> Result<Vec<ConfigChange>> {
+    let before = normalize(&Document::parse(before)?.root);
+    let after = normalize(&Document::parse(after)?.root);
+
+    let mut changes = vec![];
+    if before.name != after.name {
+        changes.push(ConfigChange {
+            path: "/".to_string(),
+            kind: ChangeKind::Modified,
+            before: Some(before.name.clone()),
+            after: Some(after.name.clone()),
+        });
+    } else {
+        diff_elements(&format!("/{}", before.name), &before, &after, &mut changes);
+    }
+    Ok(changes)
+}
+
+/// A job whose live configuration differs from the expected one
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    /// The job doesn't exist
+    Missing {
+        /// Full name of the job
+        job: String,
+    },
+    /// The configuration of the job changed
+    Changed {
+        /// Full name of the job
+        job: String,
+        /// Changes from the expected configuration to the live one
+        changes: Vec<ConfigChange>,
+    },
+}
+
+impl Jenkins {
+    /// Compare the live configuration of jobs with their expected config.xml files, keyed
+    /// by the full names of the jobs with the names of the folders containing them
+    /// separated by `/`. Jobs matching their expected configuration are not reported
+    pub fn detect_drift(&self, expected: &HashMap<String, String>) -> Result<Vec<Drift>> {
+        let mut jobs = expected.keys().collect::<Vec<_>>();
+        jobs.sort();
+
+        let mut drifts = vec![];
+        for job in jobs {
+            let live = match self.get_job_config_xml(job) {
+                Ok(live) => live,
+                Err(error) if status_of(error.as_ref()) == Some(StatusCode::NOT_FOUND) => {
+                    drifts.push(Drift::Missing { job: job.clone() });
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            let changes = config_diff(&expected[job], &live)?;
+            if !changes.is_empty() {
+                drifts.push(Drift::Changed {
+                    job: job.clone(),
+                    changes,
+                });
+            }
+        }
+        Ok(drifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXPECTED: &str = r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <description>a job</description>
+  <disabled>false</disabled>
+  <builders>
+    <hudson.tasks.Shell>
+      <command>make</command>
+    </hudson.tasks.Shell>
+  </builders>
+  <publishers/>
+</project>"#;
+
+    #[test]
+    fn ignores_formatting_and_plugin_versions() {
+        let live = r#"<project><!-- comment --><description>a job</description>
+            <disabled>false</disabled><builders><hudson.tasks.Shell plugin="shell@1.0">
+            <command>make</command></hudson.tasks.Shell></builders><publishers></publishers>
+        </project>"#;
+        assert_eq!(config_diff(EXPECTED, live).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn can_report_changes() {
+        let live = r#"<project>
+  <disabled>true</disabled>
+  <builders>
+    <hudson.tasks.Shell>
+      <command>make</command>
+    </hudson.tasks.Shell>
+    <hudson.tasks.Shell>
+      <command>make test</command>
+    </hudson.tasks.Shell>
+  </builders>
+  <publishers/>
+  <assignedNode>linux</assignedNode>
+</project>"#;
+        let changes = config_diff(EXPECTED, live).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ConfigChange {
+                    path: "/project/description".to_string(),
+                    kind: ChangeKind::Removed,
+                    before: Some("<description>a job</description>".to_string()),
+                    after: None,
+                },
+                ConfigChange {
+                    path: "/project/disabled".to_string(),
+                    kind: ChangeKind::Modified,
+                    before: Some("false".to_string()),
+                    after: Some("true".to_string()),
+                },
+                ConfigChange {
+                    path: "/project/builders/hudson.tasks.Shell[2]".to_string(),
+                    kind: ChangeKind::Added,
+                    before: None,
+                    after: Some(
+                        "<hudson.tasks.Shell><command>make test</command></hudson.tasks.Shell>"
+                            .to_string()
+                    ),
+                },
+                ConfigChange {
+                    path: "/project/assignedNode".to_string(),
+                    kind: ChangeKind::Added,
+                    before: None,
+                    after: Some("<assignedNode>linux</assignedNode>".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_detect_drift() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _same = mockito::mock("GET", "/job/folder/job/same/config.xml")
+            .with_body(EXPECTED)
+            .create();
+        let _changed = mockito::mock("GET", "/job/changed/config.xml")
+            .with_body(EXPECTED.replace("a job", "another job"))
+            .create();
+        let _missing = mockito::mock("GET", "/job/missing/config.xml")
+            .with_status(404)
+            .create();
+
+        let expected = ["folder/same", "changed", "missing"]
+            .iter()
+            .map(|job| (job.to_string(), EXPECTED.to_string()))
+            .collect();
+        let drifts = jenkins_client.detect_drift(&expected).unwrap();
+        assert_eq!(
+            drifts,
+            vec![
+                Drift::Changed {
+                    job: "changed".to_string(),
+                    changes: vec![ConfigChange {
+                        path: "/project/description".to_string(),
+                        kind: ChangeKind::Modified,
+                        before: Some("a job".to_string()),
+                        after: Some("another job".to_string()),
+                    }],
+                },
+                Drift::Missing {
+                    job: "missing".to_string()
+                },
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use reqwest::StatusCode;
 use serde::Deserialize;
 
 use crate::helpers::Class;
@@ -5,7 +6,13 @@ use crate::helpers::Class;
 use super::Job;
 use crate::action::CommonAction;
 use crate::build::{CommonBuild, ShortBuild};
+use crate::client::Result;
+use crate::client_internals::{status_of, Path};
+use crate::job::common::job_page;
+use crate::job::walker::{names_from_url, WalkedJob};
 use crate::job::ShortJob;
+use crate::xml;
+use crate::Jenkins;
 
 job_base_with_common_fields_and_impl!(
     /// A folder
@@ -18,4 +25,150 @@ job_base_with_common_fields_and_impl!(
 );
 register_class!("com.cloudbees.hudson.plugins.folder.Folder" => Folder);
 
-impl Folder {}
+impl Folder {
+    /// List the jobs of the folder, with the names of the folders containing them
+    pub fn jobs(&self) -> Vec<WalkedJob> {
+        let folders = names_from_url(&self.url);
+        self.jobs
+            .iter()
+            .map(|job| WalkedJob {
+                class: job
+                    .extra_fields
+                    .as_ref()
+                    .and_then(|extra_fields| extra_fields.get("_class"))
+                    .and_then(|class| class.as_str())
+                    .map(ToString::to_string),
+                folders: folders.clone(),
+                job: job.clone(),
+            })
+            .collect()
+    }
+
+    /// Update the config.xml file of this folder
+    pub fn update_config_xml(&self, jenkins_client: &Jenkins, config: &str) -> Result<()> {
+        let _ = jenkins_client.post_xml(
+            &job_page(jenkins_client, &self.url, "config.xml")?,
+            config.to_string(),
+            &[],
+        )?;
+        Ok(())
+    }
+}
+
+impl Jenkins {
+    /// Create a folder, from its full name with the names of the folders containing it
+    /// separated by `/`. Missing parent folders are created too
+    pub fn create_folder(
+        &self,
+        full_name: &str,
+        display_name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let (parent, name) = match full_name.rsplit_once('/') {
+            Some((parent, name)) => (Some(parent), name),
+            None => (None, full_name),
+        };
+        if let Some(parent) = parent {
+            if !self.job_exists(parent)? {
+                self.create_folder(parent, None, None)?;
+            }
+        }
+
+        let mut config = xml::Element::new(Folder::with_class());
+        if let Some(display_name) = display_name {
+            config.set_child_text("displayName", display_name);
+        }
+        if let Some(description) = description {
+            config.set_child_text("description", description);
+        }
+        let _ = self.post_xml(
+            &Path::in_folders(parent.unwrap_or(""), Path::CreateItem),
+            config.to_string(),
+            &[("name", name)],
+        )?;
+        Ok(())
+    }
+
+    /// Check if a job or a folder exists, from its full name
+    fn job_exists(&self, full_name: &str) -> Result<bool> {
+        let path = Path::for_job(full_name, |name| Path::Job {
+            name,
+            configuration: None,
+        });
+        match self.get_with_params(&path, [("tree", "name")]) {
+            Ok(_) => Ok(true),
+            Err(error) if status_of(error.as_ref()) == Some(StatusCode::NOT_FOUND) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Get the config.xml file of a job or a folder, from its full name with the names
+    /// of the folders containing it separated by `/`
+    pub fn get_job_config_xml(&self, full_name: &str) -> Result<String> {
+        Ok(self
+            .get_raw(&Path::for_job(full_name, |name| Path::JobPage {
+                name,
+                page: "config.xml",
+            }))?
+            .text()?)
+    }
+
+    /// Update the config.xml file of a job or a folder, from its full name with the
+    /// names of the folders containing it separated by `/`
+    pub fn update_job_config_xml(&self, full_name: &str, config: &str) -> Result<()> {
+        let _ = self.post_xml(
+            &Path::for_job(full_name, |name| Path::JobPage {
+                name,
+                page: "config.xml",
+            }),
+            config.to_string(),
+            &[],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn can_create_nested_folder() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _exists = mockito::mock("GET", "/job/team/api/json?tree=name")
+            .with_status(404)
+            .create();
+        let parent = mockito::mock("POST", "/createItem?name=team")
+            .match_body("<com.cloudbees.hudson.plugins.folder.Folder/>")
+            .create();
+        let child = mockito::mock("POST", "/job/team/createItem?name=project")
+            .match_body(
+                "<com.cloudbees.hudson.plugins.folder.Folder><displayName>Project</displayName><description>a &amp; b</description></com.cloudbees.hudson.plugins.folder.Folder>",
+            )
+            .create();
+
+        assert!(jenkins_client
+            .create_folder("team/project", Some("Project"), Some("a & b"))
+            .is_ok());
+        parent.assert();
+        child.assert();
+    }
+
+    #[test]
+    fn can_list_jobs_of_nested_folder() {
+        let folder: super::Folder = serde_json::from_value(serde_json::json!({
+            "name": "project",
+            "displayName": "project",
+            "url": "http://localhost:8080/job/my%20team/job/project/",
+            "actions": [],
+            "jobs": [{"name": "app", "url": "http://localhost:8080/job/my%20team/job/project/job/app/", "color": "blue"}],
+        }))
+        .unwrap();
+
+        let jobs = folder.jobs();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].full_name(), "my team/project/app");
+    }
+}
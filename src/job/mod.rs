@@ -5,7 +5,11 @@ use crate::queue::ShortQueueItem;
 use crate::Jenkins;
 
 pub mod builder;
+pub mod config;
+pub mod diff;
+pub mod patch;
 use self::builder::JobBuilder;
+pub mod walker;
 
 #[macro_use]
 mod common;
@@ -29,7 +33,11 @@ pub use self::external::ExternalJob;
 mod folder;
 pub use self::folder::Folder;
 mod multibranch_pipeline;
-pub use self::multibranch_pipeline::WorkflowMultiBranchProject;
+pub use self::multibranch_pipeline::{
+    BranchHead, BranchJob, BranchKind, WorkflowMultiBranchProject,
+};
+mod organization_folder;
+pub use self::organization_folder::OrganizationFolder;
 
 impl Jenkins {
     /// Get a `Job` from it's `job_name`
@@ -62,6 +70,25 @@ impl Jenkins {
         JobBuilder::new_from_job_name(job_name, self)
     }
 
+    /// Delete a `Job`, from its full name with the names of the folders containing it
+    /// separated by `/`
+    pub fn delete_job(&self, full_name: &str) -> Result<()> {
+        let _ = self.post(&Path::for_job(full_name, |name| Path::DeleteJob { name }))?;
+        Ok(())
+    }
+
+    /// Create a `Job` from its config.xml file, from its full name with the names of the
+    /// folders containing it separated by `/`
+    pub fn create_job_from_xml(&self, full_name: &str, config: &str) -> Result<()> {
+        let (folders, name) = full_name.rsplit_once('/').unwrap_or(("", full_name));
+        let _ = self.post_xml(
+            &Path::in_folders(folders, Path::CreateItem),
+            config.to_string(),
+            &[("name", name)],
+        )?;
+        Ok(())
+    }
+
     /// Poll SCM of a `Job` from it's `job_name`
     pub fn poll_scm_job<'a, J>(&self, job_name: J) -> Result<()>
     where
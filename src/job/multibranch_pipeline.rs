@@ -1,11 +1,21 @@
+use reqwest::StatusCode;
 use serde::Deserialize;
 
 use crate::helpers::Class;
 
-use super::Job;
-use crate::action::CommonAction;
+use super::{BallColor, Job};
+use crate::action::{
+    git, CommonAction, ContributorMetadataAction, GitBuildData, ObjectMetadataAction,
+    PrimaryInstanceMetadataAction,
+};
 use crate::build::{CommonBuild, ShortBuild};
-use crate::job::ShortJob;
+use crate::client::{AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::{status_of, InternalAdvancedQueryParams};
+use crate::job::common::job_page;
+use crate::job::{ShortJob, WorkflowJob};
+use crate::view::ShortView;
+use crate::xml;
+use crate::Jenkins;
 
 job_base_with_common_fields_and_impl!(
     /// A pipeline project
@@ -14,8 +24,663 @@ job_base_with_common_fields_and_impl!(
     pub struct WorkflowMultiBranchProject {
         /// List of the jobs in the pipline
         pub jobs: Vec<ShortJob>,
+        /// Views of the project, grouping branches, pull requests and tags
+        #[serde(default)]
+        pub views: Vec<ShortView>,
     }
 );
 register_class!("org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject" => WorkflowMultiBranchProject);
 
-impl WorkflowMultiBranchProject {}
+/// Kind of SCM head built by a branch job
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BranchKind {
+    /// A branch
+    Branch,
+    /// A pull request, or merge request
+    PullRequest,
+    /// A tag
+    Tag,
+}
+
+/// `_class` of the property of a branch job holding its SCM head
+const BRANCH_JOB_PROPERTY: &str = "org.jenkinsci.plugins.workflow.multibranch.BranchJobProperty";
+/// `_class` of the action marking the job of a pull request, with older branch sources
+const CHANGE_REQUEST_ACTION: &str = "jenkins.scm.api.actions.ChangeRequestAction";
+/// `_class` of the action of a build holding the revision of the SCM head built
+const SCM_REVISION_ACTION: &str = "jenkins.scm.api.SCMRevisionAction";
+
+/// Path of the SCM head in the config.xml file of a branch job
+const BRANCH_HEAD_PATH: [&str; 4] = ["properties", BRANCH_JOB_PROPERTY, "branch", "head"];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HeadNode {
+    #[serde(rename = "_class")]
+    class: Option<String>,
+    source_branch: Option<String>,
+    target: Option<TargetNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TargetNode {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchPropertyNode {
+    head: Option<HeadNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PropertyNode {
+    #[serde(rename = "_class")]
+    class: Option<String>,
+    branch: Option<BranchPropertyNode>,
+}
+
+/// Get the SCM head of a branch job from its properties
+fn branch_head(properties: &[Option<PropertyNode>]) -> Option<&HeadNode> {
+    properties
+        .iter()
+        .flatten()
+        .find(|property| property.class.as_deref() == Some(BRANCH_JOB_PROPERTY))
+        .and_then(|property| property.branch.as_ref())
+        .and_then(|branch| branch.head.as_ref())
+}
+
+/// Tree of the SCM head of a branch job, in its properties
+fn branch_head_tree() -> TreeBuilder {
+    TreeBuilder::object("property")
+        .with_subfield("_class")
+        .with_subfield(
+            TreeBuilder::object("branch").with_subfield(
+                TreeBuilder::object("head")
+                    .with_subfield("_class")
+                    .with_subfield("sourceBranch")
+                    .with_subfield(TreeBuilder::object("target").with_subfield("name")),
+            ),
+        )
+}
+
+impl BranchKind {
+    /// Kind of an SCM head. Heads of pull requests have a target branch and metadata on
+    /// their contributor, and heads of tags are implemented by a class `*TagSCMHead`
+    fn of_head(head: Option<&HeadNode>, actions: &[&CommonAction]) -> Self {
+        let change_request = matches!(
+            head,
+            Some(HeadNode {
+                target: Some(_),
+                ..
+            })
+        ) || actions.iter().any(|action| {
+            action.class.as_deref() == Some(CHANGE_REQUEST_ACTION)
+                || action.as_variant::<ContributorMetadataAction>().is_ok()
+        });
+        if change_request {
+            BranchKind::PullRequest
+        } else if matches!(
+            head.and_then(|head| head.class.as_deref()),
+            Some(class) if class.ends_with("TagSCMHead")
+        ) {
+            BranchKind::Tag
+        } else {
+            BranchKind::Branch
+        }
+    }
+}
+
+/// A job of a `WorkflowMultiBranchProject`, building a branch, a pull request or a tag,
+/// with its metadata from the branch source
+#[derive(Debug, Clone)]
+pub struct BranchJob {
+    /// Kind of SCM head
+    pub kind: BranchKind,
+    /// Name of the job, like `main` or `PR-12`
+    pub name: String,
+    /// Display name of the job
+    pub display_name: Option<String>,
+    /// URL for the job
+    pub url: String,
+    /// Ball Color for the status of the job
+    pub color: Option<BallColor>,
+    /// Title of the pull request, or display name of the branch
+    pub title: Option<String>,
+    /// Description of the SCM object
+    pub description: Option<String>,
+    /// URL of the branch or pull request on the SCM
+    pub object_url: Option<String>,
+    /// ID of the author of the pull request
+    pub author: Option<String>,
+    /// Display name of the author of the pull request
+    pub author_display_name: Option<String>,
+    /// Email of the author of the pull request
+    pub author_email: Option<String>,
+    /// Target branch of the pull request
+    pub target_branch: Option<String>,
+    /// Is this the primary branch of the project
+    pub primary: bool,
+}
+
+/// Details of the SCM head built by a branch job, read from the job and its last build
+#[derive(Debug, Clone, Default)]
+pub struct BranchHead {
+    /// Source branch of the pull request
+    pub source_branch: Option<String>,
+    /// Target branch of the pull request
+    pub target_branch: Option<String>,
+    /// Commit of the last build. For pull requests built merged with their target, it's
+    /// the merge commit
+    pub head_commit: Option<String>,
+    /// Commit of the target branch the pull request was merged with, when built merged
+    pub base_commit: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RevisionNode {
+    hash: Option<String>,
+    pull_hash: Option<String>,
+    base_hash: Option<String>,
+}
+
+/// An action of a build, either git data of a checkout or the revision of the SCM head
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildActionNode {
+    #[serde(rename = "_class")]
+    class: Option<String>,
+    last_built_revision: Option<git::Revision>,
+    revision: Option<RevisionNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuildNode {
+    #[serde(default)]
+    actions: Vec<Option<BuildActionNode>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BranchJobNode {
+    #[serde(default)]
+    property: Vec<Option<PropertyNode>>,
+    last_build: Option<BuildNode>,
+}
+
+impl BranchJob {
+    /// Number of the pull request, from the name of the job like `PR-12`
+    pub fn pull_request_number(&self) -> Option<u32> {
+        if self.kind != BranchKind::PullRequest {
+            return None;
+        }
+        self.name.rsplit('-').next()?.parse().ok()
+    }
+
+    /// Get the full details of the `WorkflowJob` building this branch
+    pub fn get_full_job(&self, jenkins_client: &Jenkins) -> Result<WorkflowJob> {
+        Ok(jenkins_client
+            .get(&jenkins_client.url_to_path(&self.url))?
+            .json()?)
+    }
+
+    /// Read the source and target branches of a pull request from the config.xml file of
+    /// the job, which needs the permission to read its configuration. They are `None` if
+    /// it can't be read
+    fn head_from_config(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> Result<(Option<String>, Option<String>)> {
+        let config =
+            match jenkins_client.get_raw(&job_page(jenkins_client, &self.url, "config.xml")?) {
+                Ok(response) => xml::Document::parse(&response.text()?)?,
+                Err(error)
+                    if status_of(error.as_ref()) == Some(StatusCode::FORBIDDEN)
+                        || status_of(error.as_ref()) == Some(StatusCode::NOT_FOUND) =>
+                {
+                    return Ok((None, None))
+                }
+                Err(error) => return Err(error),
+            };
+        let head = BRANCH_HEAD_PATH
+            .iter()
+            .try_fold(&config.root, |element, name| element.child(name));
+        let text = |element: Option<&xml::Element>| {
+            element
+                .map(xml::Element::text)
+                .filter(|text| !text.is_empty())
+        };
+        Ok((
+            text(head.and_then(|head| head.child("sourceBranch"))),
+            text(
+                head.and_then(|head| head.child("target"))
+                    .and_then(|target| target.child("name")),
+            ),
+        ))
+    }
+
+    /// Get the details of the SCM head built, from the job and its last build. The commit
+    /// built is the one of the git checkout of the branch, ignoring other checkouts like
+    /// shared libraries. The source and target branches of a pull request are read from
+    /// the configuration of the job only if Jenkins doesn't expose them
+    pub fn get_head(&self, jenkins_client: &Jenkins) -> Result<BranchHead> {
+        let job: BranchJobNode = jenkins_client
+            .get_with_params(
+                &jenkins_client.url_to_path(&self.url),
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                    TreeBuilder::new()
+                        .with_field(branch_head_tree())
+                        .with_field(
+                            TreeBuilder::object("lastBuild").with_subfield(
+                                TreeBuilder::object("actions")
+                                    .with_subfield("_class")
+                                    .with_subfield(
+                                        TreeBuilder::object("lastBuiltRevision")
+                                            .with_subfield("SHA1")
+                                            .with_subfield(
+                                                TreeBuilder::object("branch")
+                                                    .with_subfield("SHA1")
+                                                    .with_subfield("name"),
+                                            ),
+                                    )
+                                    .with_subfield(
+                                        TreeBuilder::object("revision")
+                                            .with_subfield("hash")
+                                            .with_subfield("pullHash")
+                                            .with_subfield("baseHash"),
+                                    ),
+                            ),
+                        )
+                        .build(),
+                )),
+            )?
+            .json()?;
+
+        let head = branch_head(&job.property);
+        let mut source_branch = head.and_then(|head| head.source_branch.clone());
+        let mut target_branch = head
+            .and_then(|head| head.target.as_ref())
+            .and_then(|target| target.name.clone());
+        if self.kind == BranchKind::PullRequest
+            && (source_branch.is_none() || target_branch.is_none())
+        {
+            let (config_source, config_target) = self.head_from_config(jenkins_client)?;
+            source_branch = source_branch.or(config_source);
+            target_branch = target_branch.or(config_target);
+        }
+
+        let actions = job
+            .last_build
+            .into_iter()
+            .flat_map(|build| build.actions)
+            .flatten()
+            .collect::<Vec<_>>();
+        let revision = actions
+            .iter()
+            .filter(|action| action.class.as_deref() == Some(SCM_REVISION_ACTION))
+            .find_map(|action| action.revision.as_ref());
+        // the git plugin names the branch checked out for a branch job like the job
+        let checkout = actions
+            .iter()
+            .filter(|action| action.class.as_deref() == Some(GitBuildData::with_class()))
+            .filter_map(|action| action.last_built_revision.as_ref())
+            .find(|revision| {
+                revision.branch.iter().any(|branch| {
+                    branch.name == self.name || branch.name.ends_with(&format!("/{}", self.name))
+                })
+            });
+
+        Ok(BranchHead {
+            source_branch,
+            target_branch,
+            head_commit: checkout.map(|checkout| checkout.sha1.clone()).or_else(|| {
+                revision.and_then(|revision| {
+                    revision.pull_hash.clone().or_else(|| revision.hash.clone())
+                })
+            }),
+            base_commit: revision.and_then(|revision| revision.base_hash.clone()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BranchNode {
+    name: String,
+    display_name: Option<String>,
+    url: String,
+    color: Option<BallColor>,
+    #[serde(default)]
+    actions: Vec<Option<CommonAction>>,
+    #[serde(default)]
+    property: Vec<Option<PropertyNode>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchTree {
+    #[serde(default)]
+    jobs: Vec<BranchNode>,
+}
+
+/// Trigger the scan of a computed folder, like a multibranch project
+pub(crate) fn trigger_scan(jenkins_client: &Jenkins, url: &str) -> Result<()> {
+    let _ = jenkins_client.post_with_body(
+        &job_page(jenkins_client, url, "build")?,
+        "",
+        &[("delay", "0")],
+    )?;
+    Ok(())
+}
+
+impl WorkflowMultiBranchProject {
+    /// List the branches, pull requests and tags of the project, with their metadata
+    pub fn branches(&self, jenkins_client: &Jenkins) -> Result<Vec<BranchJob>> {
+        let tree: BranchTree = jenkins_client
+            .get_with_params(
+                &jenkins_client.url_to_path(&self.url),
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                    TreeBuilder::new()
+                        .with_field(
+                            TreeBuilder::object("jobs")
+                                .with_subfield("name")
+                                .with_subfield("displayName")
+                                .with_subfield("url")
+                                .with_subfield("color")
+                                .with_subfield(
+                                    TreeBuilder::object("actions")
+                                        .with_subfield("_class")
+                                        .with_subfield("objectDescription")
+                                        .with_subfield("objectDisplayName")
+                                        .with_subfield("objectUrl")
+                                        .with_subfield("contributor")
+                                        .with_subfield("contributorDisplayName")
+                                        .with_subfield("contributorEmail"),
+                                )
+                                .with_subfield(branch_head_tree()),
+                        )
+                        .build(),
+                )),
+            )?
+            .json()?;
+
+        Ok(tree
+            .jobs
+            .iter()
+            .map(|job| {
+                let actions = job.actions.iter().flatten().collect::<Vec<_>>();
+                let head = branch_head(&job.property);
+                let object = actions
+                    .iter()
+                    .find_map(|action| action.as_variant::<ObjectMetadataAction>().ok());
+                let contributor = actions
+                    .iter()
+                    .find_map(|action| action.as_variant::<ContributorMetadataAction>().ok());
+                BranchJob {
+                    kind: BranchKind::of_head(head, &actions),
+                    name: job.name.clone(),
+                    display_name: job.display_name.clone(),
+                    url: job.url.clone(),
+                    color: job.color,
+                    title: object
+                        .as_ref()
+                        .and_then(|object| object.object_display_name.clone()),
+                    description: object
+                        .as_ref()
+                        .and_then(|object| object.object_description.clone()),
+                    object_url: object.and_then(|object| object.object_url),
+                    author: contributor
+                        .as_ref()
+                        .and_then(|contributor| contributor.contributor.clone()),
+                    author_display_name: contributor
+                        .as_ref()
+                        .and_then(|contributor| contributor.contributor_display_name.clone()),
+                    author_email: contributor.and_then(|contributor| contributor.contributor_email),
+                    target_branch: head
+                        .and_then(|head| head.target.as_ref())
+                        .and_then(|target| target.name.clone()),
+                    primary: actions
+                        .iter()
+                        .any(|action| action.as_variant::<PrimaryInstanceMetadataAction>().is_ok()),
+                }
+            })
+            .collect())
+    }
+
+    /// Find the job building a pull request, from its number
+    pub fn find_pull_request(
+        &self,
+        jenkins_client: &Jenkins,
+        number: u32,
+    ) -> Result<Option<BranchJob>> {
+        Ok(self
+            .branches(jenkins_client)?
+            .into_iter()
+            .find(|branch| branch.pull_request_number() == Some(number)))
+    }
+
+    /// Trigger a scan of the branches of the project
+    pub fn trigger_scan(&self, jenkins_client: &Jenkins) -> Result<()> {
+        trigger_scan(jenkins_client, &self.url)
+    }
+
+    /// Get the log of the last branch indexing
+    pub fn get_indexing_log(&self, jenkins_client: &Jenkins) -> Result<String> {
+        Ok(jenkins_client
+            .get_raw(&job_page(
+                jenkins_client,
+                &self.url,
+                "indexing/consoleText",
+            )?)?
+            .text()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn branch_property(class: &str, target: Option<&str>) -> String {
+        format!(
+            r#"{{"_class": "org.jenkinsci.plugins.workflow.multibranch.BranchJobProperty",
+                "branch": {{"head": {{"_class": "{}", "target": {}}}}}}}"#,
+            class,
+            target.map_or("null".to_string(), |target| format!(
+                r#"{{"name": "{}"}}"#,
+                target
+            ))
+        )
+    }
+
+    #[test]
+    fn can_list_branches_and_pull_requests() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/job/project/api/json\?tree=jobs".to_string()),
+        )
+        .with_body(format!(
+            r#"{{
+                "jobs": [
+                    {{"name": "main", "url": "/job/project/job/main/", "color": "blue", "actions": [
+                        {{"_class": "jenkins.scm.api.metadata.PrimaryInstanceMetadataAction"}}
+                    ], "property": [{}]}},
+                    {{"name": "PR-12", "url": "/job/project/job/PR-12/", "color": "red", "actions": [
+                        {{"_class": "jenkins.scm.api.metadata.ObjectMetadataAction", "objectDisplayName": "Fix the build", "objectUrl": "https://github.com/org/project/pull/12"}},
+                        {{"_class": "jenkins.scm.api.metadata.ContributorMetadataAction", "contributor": "octocat", "contributorEmail": "octocat@github.com"}},
+                        {{}}
+                    ], "property": [{{"_class": "hudson.model.ParametersDefinitionProperty"}}, {}]}},
+                    {{"name": "v1.0", "url": "/job/project/job/v1.0/", "actions": [], "property": [{}]}},
+                    {{"name": "PR-13", "url": "/job/project/job/PR-13/", "actions": [
+                        {{"_class": "jenkins.scm.api.actions.ChangeRequestAction"}}
+                    ]}}
+                ]
+            }}"#,
+            branch_property("jenkins.plugins.git.GitBranchSCMHead", None),
+            branch_property(
+                "org.jenkinsci.plugins.github_branch_source.PullRequestSCMHead",
+                Some("main")
+            ),
+            branch_property(
+                "org.jenkinsci.plugins.github_branch_source.GitHubTagSCMHead",
+                None
+            ),
+        ))
+        .create();
+        let configs = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/job/project/job/.*/config.xml$".to_string()),
+        )
+        .expect(0)
+        .create();
+
+        let project: WorkflowMultiBranchProject = serde_json::from_value(serde_json::json!({
+            "name": "project",
+            "displayName": "project",
+            "url": format!("{}/job/project/", mockito::server_url()),
+            "actions": [],
+            "jobs": [],
+        }))
+        .unwrap();
+
+        let branches = project.branches(&jenkins_client).unwrap();
+        assert_eq!(branches.len(), 4);
+        assert!(branches[0].primary);
+        assert_eq!(branches[0].kind, BranchKind::Branch);
+        assert_eq!(branches[2].kind, BranchKind::Tag);
+        assert_eq!(branches[3].kind, BranchKind::PullRequest);
+
+        let pull_request = project
+            .find_pull_request(&jenkins_client, 12)
+            .unwrap()
+            .unwrap();
+        assert_eq!(pull_request.kind, BranchKind::PullRequest);
+        assert_eq!(pull_request.title, Some("Fix the build".to_string()));
+        assert_eq!(pull_request.author, Some("octocat".to_string()));
+        assert_eq!(pull_request.target_branch, Some("main".to_string()));
+        configs.assert();
+    }
+
+    fn pull_request(name: &str) -> BranchJob {
+        BranchJob {
+            kind: BranchKind::PullRequest,
+            name: name.to_string(),
+            display_name: None,
+            url: format!("{}/job/project/job/{}/", mockito::server_url(), name),
+            color: None,
+            title: None,
+            description: None,
+            object_url: None,
+            author: None,
+            author_display_name: None,
+            author_email: None,
+            target_branch: None,
+            primary: false,
+        }
+    }
+
+    #[test]
+    fn can_get_head_of_pull_request() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _job = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/job/project/job/PR-7/api/json\?tree=property".to_string()),
+        )
+        .with_body(format!(
+            r#"{{
+                "property": [{}],
+                "lastBuild": {{"actions": [
+                    {{"_class": "hudson.plugins.git.util.BuildData", "lastBuiltRevision": {{
+                        "SHA1": "1111111", "branch": [{{"SHA1": "1111111", "name": "master"}}]
+                    }}}},
+                    {{"_class": "hudson.plugins.git.util.BuildData", "lastBuiltRevision": {{
+                        "SHA1": "2222222", "branch": [{{"SHA1": "2222222", "name": "PR-7"}}]
+                    }}}},
+                    {{"_class": "jenkins.scm.api.SCMRevisionAction", "revision": {{
+                        "_class": "org.jenkinsci.plugins.github_branch_source.PullRequestSCMRevision",
+                        "pullHash": "3333333", "baseHash": "4444444"
+                    }}}},
+                    {{}}
+                ]}}
+            }}"#,
+            branch_property(
+                "org.jenkinsci.plugins.github_branch_source.PullRequestSCMHead",
+                Some("main")
+            )
+        ))
+        .create();
+        let _config = mockito::mock("GET", "/job/project/job/PR-7/config.xml")
+            .with_body(
+                r#"<flow-definition>
+                    <actions><deploy><target><name>staging</name></target></deploy></actions>
+                    <properties>
+                        <org.jenkinsci.plugins.workflow.multibranch.BranchJobProperty>
+                            <branch>
+                                <head class="org.jenkinsci.plugins.github_branch_source.PullRequestSCMHead">
+                                    <name>PR-7</name>
+                                    <sourceBranch>feature</sourceBranch>
+                                    <target class="jenkins.plugins.git.GitBranchSCMHead"><name>main</name></target>
+                                </head>
+                            </branch>
+                        </org.jenkinsci.plugins.workflow.multibranch.BranchJobProperty>
+                    </properties>
+                </flow-definition>"#,
+            )
+            .create();
+
+        let head = pull_request("PR-7").get_head(&jenkins_client).unwrap();
+        assert_eq!(head.source_branch, Some("feature".to_string()));
+        assert_eq!(head.target_branch, Some("main".to_string()));
+        assert_eq!(head.head_commit, Some("2222222".to_string()));
+        assert_eq!(head.base_commit, Some("4444444".to_string()));
+    }
+
+    #[test]
+    fn can_get_head_without_reading_config() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _job = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/job/project/job/PR-8/api/json\?tree=property".to_string()),
+        )
+        .with_body(format!(
+            r#"{{"property": [{}], "lastBuild": null}}"#,
+            branch_property(
+                "org.jenkinsci.plugins.github_branch_source.PullRequestSCMHead",
+                Some("main")
+            )
+        ))
+        .create();
+        let _config = mockito::mock("GET", "/job/project/job/PR-8/config.xml")
+            .with_status(403)
+            .create();
+
+        let head = pull_request("PR-8").get_head(&jenkins_client).unwrap();
+        assert_eq!(head.source_branch, None);
+        assert_eq!(head.target_branch, Some("main".to_string()));
+        assert_eq!(head.head_commit, None);
+    }
+
+    #[test]
+    fn can_trigger_scan() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = mockito::mock("POST", "/job/folder/job/project/build?delay=0").create();
+
+        assert!(trigger_scan(
+            &jenkins_client,
+            &format!("{}/job/folder/job/project/", mockito::server_url())
+        )
+        .is_ok());
+        mock.assert();
+    }
+}
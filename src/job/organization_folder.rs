@@ -0,0 +1,106 @@
+use serde::Deserialize;
+
+use crate::helpers::Class;
+
+use super::Job;
+use crate::action::CommonAction;
+use crate::build::{CommonBuild, ShortBuild};
+use crate::client::Result;
+use crate::job::common::job_page;
+use crate::job::multibranch_pipeline::trigger_scan;
+use crate::job::{ShortJob, WorkflowMultiBranchProject};
+use crate::Jenkins;
+
+job_base_with_common_fields_and_impl!(
+    /// An organization folder, discovering the repositories of an organization on a SCM
+    /// server like GitHub or Bitbucket
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct OrganizationFolder {
+        /// List of the repositories discovered, each one as a multibranch project
+        pub jobs: Vec<ShortJob<WorkflowMultiBranchProject>>,
+    }
+);
+register_class!("jenkins.branch.OrganizationFolder" => OrganizationFolder);
+
+impl OrganizationFolder {
+    /// Get the multibranch project of a repository of the organization, from the
+    /// repository name
+    pub fn get_repository(
+        &self,
+        jenkins_client: &Jenkins,
+        repository: &str,
+    ) -> Result<Option<WorkflowMultiBranchProject>> {
+        self.jobs
+            .iter()
+            .find(|job| job.name == repository)
+            .map(|job| job.get_full_job(jenkins_client))
+            .transpose()
+    }
+
+    /// Trigger a scan of the repositories of the organization
+    pub fn trigger_scan(&self, jenkins_client: &Jenkins) -> Result<()> {
+        trigger_scan(jenkins_client, &self.url)
+    }
+
+    /// Get the log of the last scan of the organization
+    pub fn get_scan_log(&self, jenkins_client: &Jenkins) -> Result<String> {
+        Ok(jenkins_client
+            .get_raw(&job_page(
+                jenkins_client,
+                &self.url,
+                "computation/consoleText",
+            )?)?
+            .text()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_get_repository_and_scan_log() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _log = mockito::mock("GET", "/job/org/computation/consoleText")
+            .with_body("Finished: SUCCESS")
+            .create();
+        let _repository = mockito::mock("GET", "/job/org/job/project/api/json?depth=1")
+            .with_body(
+                r#"{"_class": "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject",
+                    "name": "project", "displayName": "project", "fullName": "org/project",
+                    "fullDisplayName": "org » project", "url": "/job/org/job/project/",
+                    "buildable": false, "actions": [], "healthReport": [], "jobs": []}"#,
+            )
+            .create();
+
+        let organization: OrganizationFolder = serde_json::from_value(serde_json::json!({
+            "name": "org",
+            "displayName": "org",
+            "actions": [],
+            "url": format!("{}/job/org/", mockito::server_url()),
+            "jobs": [{"name": "project", "url": format!("{}/job/org/job/project/", mockito::server_url())}],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            organization.get_scan_log(&jenkins_client).unwrap(),
+            "Finished: SUCCESS"
+        );
+        assert!(organization
+            .get_repository(&jenkins_client, "missing")
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            organization
+                .get_repository(&jenkins_client, "project")
+                .unwrap()
+                .unwrap()
+                .name,
+            "project"
+        );
+    }
+}
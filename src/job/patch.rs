@@ -0,0 +1,298 @@
+//! Helpers to update common fields of the configuration of a job
+
+use crate::client::{self, Result};
+use crate::client_internals::Path;
+use crate::xml::{self, Element};
+use crate::Jenkins;
+
+const PIPELINE_ROOT: &str = "flow-definition";
+const BUILD_DISCARDER_PROPERTY: &str = "jenkins.model.BuildDiscarderProperty";
+const DISABLE_CONCURRENT_BUILDS_PROPERTY: &str =
+    "org.jenkinsci.plugins.workflow.job.properties.DisableConcurrentBuildsJobProperty";
+
+/// Strategy to discard old builds, using `hudson.tasks.LogRotator`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BuildDiscarder {
+    /// Number of days to keep builds
+    pub days_to_keep: Option<u32>,
+    /// Number of builds to keep
+    pub num_to_keep: Option<u32>,
+    /// Number of days to keep the artifacts of builds
+    pub artifact_days_to_keep: Option<u32>,
+    /// Number of builds to keep the artifacts of
+    pub artifact_num_to_keep: Option<u32>,
+}
+
+impl BuildDiscarder {
+    fn to_element(self) -> Element {
+        let mut strategy = Element::new("strategy");
+        strategy
+            .attributes
+            .push(("class".to_string(), "hudson.tasks.LogRotator".to_string()));
+        for (name, value) in &[
+            ("daysToKeep", self.days_to_keep),
+            ("numToKeep", self.num_to_keep),
+            ("artifactDaysToKeep", self.artifact_days_to_keep),
+            ("artifactNumToKeep", self.artifact_num_to_keep),
+        ] {
+            strategy.push_child(Element::with_text(
+                name,
+                &value.map(|value| value as i64).unwrap_or(-1).to_string(),
+            ));
+        }
+        let mut property = Element::new(BUILD_DISCARDER_PROPERTY);
+        property.push_child(strategy);
+        property
+    }
+}
+
+/// Changes to apply to the configuration of a job. Fields left unset are not changed
+#[derive(Debug, Clone, Default)]
+pub struct JobConfigPatch<'a> {
+    display_name: Option<Option<&'a str>>,
+    disabled: Option<bool>,
+    concurrent_builds: Option<bool>,
+    label_expression: Option<Option<&'a str>>,
+    build_discarder: Option<Option<BuildDiscarder>>,
+}
+
+impl<'a> JobConfigPatch<'a> {
+    /// Create an empty patch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the display name, or remove it with `None`
+    pub fn with_display_name(mut self, display_name: Option<&'a str>) -> Self {
+        self.display_name = Some(display_name);
+        self
+    }
+
+    /// Disable or enable the job
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// Allow or forbid concurrent builds
+    pub fn with_concurrent_builds(mut self, concurrent_builds: bool) -> Self {
+        self.concurrent_builds = Some(concurrent_builds);
+        self
+    }
+
+    /// Restrict where the job can run with a label expression, or remove the
+    /// restriction with `None`. Not supported by pipelines, which select their agents
+    /// in the Jenkinsfile
+    pub fn with_label_expression(mut self, label_expression: Option<&'a str>) -> Self {
+        self.label_expression = Some(label_expression);
+        self
+    }
+
+    /// Set the strategy to discard old builds, or keep all builds with `None`
+    pub fn with_build_discarder(mut self, build_discarder: Option<BuildDiscarder>) -> Self {
+        self.build_discarder = Some(build_discarder);
+        self
+    }
+
+    /// Apply the patch to a config.xml file
+    pub fn apply(&self, config: &str) -> Result<String> {
+        let mut config = xml::Document::parse(config)?;
+        let root = &mut config.root;
+        let is_pipeline = root.name == PIPELINE_ROOT;
+
+        if let Some(display_name) = self.display_name {
+            match display_name {
+                Some(display_name) => root.set_child_text("displayName", display_name),
+                None => root.remove_child("displayName"),
+            }
+        }
+        if let Some(disabled) = self.disabled {
+            root.set_child_text("disabled", &disabled.to_string());
+        }
+        if let Some(concurrent_builds) = self.concurrent_builds {
+            if is_pipeline {
+                let properties = properties(root);
+                properties.remove_child(DISABLE_CONCURRENT_BUILDS_PROPERTY);
+                if !concurrent_builds {
+                    properties.push_child(Element::new(DISABLE_CONCURRENT_BUILDS_PROPERTY));
+                }
+            } else {
+                root.set_child_text("concurrentBuild", &concurrent_builds.to_string());
+            }
+        }
+        if let Some(label_expression) = self.label_expression {
+            if is_pipeline {
+                return Err(client::Error::UnsupportedConfigField {
+                    field: "label expression",
+                    root: root.name.clone(),
+                }
+                .into());
+            }
+            match label_expression {
+                Some(label_expression) => {
+                    root.set_child_text("assignedNode", label_expression);
+                    root.set_child_text("canRoam", "false");
+                }
+                None => {
+                    root.remove_child("assignedNode");
+                    root.set_child_text("canRoam", "true");
+                }
+            }
+        }
+        if let Some(build_discarder) = self.build_discarder {
+            root.remove_child("logRotator");
+            let properties = properties(root);
+            properties.remove_child(BUILD_DISCARDER_PROPERTY);
+            if let Some(build_discarder) = build_discarder {
+                properties.push_child(build_discarder.to_element());
+            }
+        }
+
+        Ok(config.to_string())
+    }
+}
+
+/// Get the `properties` element, adding it if it's missing
+fn properties(root: &mut Element) -> &mut Element {
+    if root.child("properties").is_none() {
+        root.push_child(Element::new("properties"));
+    }
+    root.child_mut("properties").unwrap()
+}
+
+/// Apply a patch to the config.xml file at `path`. The configuration is read again
+/// before being updated, and the update is aborted if it changed in between. Jenkins has
+/// no conditional update, so a change made between this second read and the update is
+/// still overwritten
+pub(crate) fn patch_config(
+    jenkins_client: &Jenkins,
+    path: &Path,
+    job: &str,
+    patch: &JobConfigPatch,
+) -> Result<()> {
+    let original = jenkins_client.get_raw(path)?.text()?;
+    let patched = patch.apply(&original)?;
+    if patched == original {
+        return Ok(());
+    }
+    if jenkins_client.get_raw(path)?.text()? != original {
+        return Err(client::Error::ConfigConflict {
+            job: job.to_string(),
+        }
+        .into());
+    }
+    let _ = jenkins_client.post_xml(path, patched, &[])?;
+    Ok(())
+}
+
+impl Jenkins {
+    /// Apply a patch to the configuration of a job, from its full name with the names
+    /// of the folders containing it separated by `/`. The detection of concurrent changes
+    /// is best effort: it fails with `ConfigConflict` if the configuration was changed
+    /// by someone else while the patch was applied, but a change made just before the
+    /// update is sent is still overwritten
+    pub fn patch_job_config(&self, full_name: &str, patch: &JobConfigPatch) -> Result<()> {
+        patch_config(
+            self,
+            &Path::for_job(full_name, |name| Path::JobPage {
+                name,
+                page: "config.xml",
+            }),
+            full_name,
+            patch,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static FREESTYLE: &str = r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <description>a job</description>
+  <logRotator class="hudson.tasks.LogRotator">
+    <daysToKeep>3</daysToKeep>
+  </logRotator>
+  <keepDependencies>false</keepDependencies>
+  <properties/>
+  <canRoam>true</canRoam>
+  <disabled>false</disabled>
+  <concurrentBuild>false</concurrentBuild>
+  <builders/>
+</project>"#;
+
+    static PIPELINE: &str = r#"<?xml version='1.1' encoding='UTF-8'?>
+<flow-definition plugin="workflow-job@2.40">
+  <properties>
+    <org.jenkinsci.plugins.workflow.job.properties.DisableConcurrentBuildsJobProperty/>
+  </properties>
+  <disabled>false</disabled>
+</flow-definition>"#;
+
+    #[test]
+    fn can_patch_freestyle_config() {
+        let patched = JobConfigPatch::new()
+            .with_disabled(true)
+            .with_concurrent_builds(true)
+            .with_label_expression(Some("linux && docker"))
+            .with_build_discarder(Some(BuildDiscarder {
+                num_to_keep: Some(10),
+                ..Default::default()
+            }))
+            .apply(FREESTYLE)
+            .unwrap();
+        let config = xml::Document::parse(&patched).unwrap();
+
+        assert_eq!(config.root.child("disabled").unwrap().text(), "true");
+        assert_eq!(config.root.child("concurrentBuild").unwrap().text(), "true");
+        assert_eq!(
+            config.root.child("assignedNode").unwrap().text(),
+            "linux && docker"
+        );
+        assert_eq!(config.root.child("canRoam").unwrap().text(), "false");
+        assert!(config.root.child("logRotator").is_none());
+        assert_eq!(config.root.descendant("numToKeep").unwrap().text(), "10");
+        assert_eq!(config.root.descendant("daysToKeep").unwrap().text(), "-1");
+        assert_eq!(config.root.child("description").unwrap().text(), "a job");
+    }
+
+    #[test]
+    fn can_patch_pipeline_config() {
+        let patched = JobConfigPatch::new()
+            .with_concurrent_builds(true)
+            .apply(PIPELINE)
+            .unwrap();
+        assert!(!patched.contains("DisableConcurrentBuildsJobProperty"));
+        assert!(patched.starts_with("<?xml version='1.1' encoding='UTF-8'?>\n<flow-definition"));
+
+        assert!(JobConfigPatch::new()
+            .with_label_expression(Some("linux"))
+            .apply(PIPELINE)
+            .is_err());
+    }
+
+    #[test]
+    fn can_detect_conflict() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _first = mockito::mock("GET", "/job/folder/job/job/config.xml")
+            .with_body(FREESTYLE)
+            .expect(1)
+            .create();
+        let _second = mockito::mock("GET", "/job/folder/job/job/config.xml")
+            .with_body(PIPELINE)
+            .create();
+        let update = mockito::mock("POST", "/job/folder/job/job/config.xml")
+            .expect(0)
+            .create();
+
+        let result = jenkins_client
+            .patch_job_config("folder/job", &JobConfigPatch::new().with_disabled(true));
+        assert!(result.is_err());
+        update.assert();
+    }
+}
@@ -0,0 +1,308 @@
+//! Helper to list jobs recursively, through folders and multibranch projects
+
+use regex::Regex;
+use serde::{self, Deserialize};
+
+use crate::client::{AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, Path};
+use crate::helpers::Class;
+use crate::job::{Job, ShortJob};
+use crate::Jenkins;
+
+/// Default number of levels of folders traversed when listing jobs
+pub const DEFAULT_MAX_DEPTH: usize = 10;
+
+/// A job found while walking through Jenkins, with the folders leading to it
+#[derive(Debug, Clone)]
+pub struct WalkedJob {
+    /// _class provided by Jenkins
+    pub class: Option<String>,
+    /// Names of the folders containing the job, from the top level
+    pub folders: Vec<String>,
+    /// The job
+    pub job: ShortJob,
+}
+
+impl WalkedJob {
+    /// Full name of the job, with the names of its folders separated by `/`
+    pub fn full_name(&self) -> String {
+        self.folders
+            .iter()
+            .chain(std::iter::once(&self.job.name))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Depth of the job, 0 for a job at the top level
+    pub fn depth(&self) -> usize {
+        self.folders.len()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JobTree {
+    #[serde(default)]
+    jobs: Vec<JobTreeNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobTreeNode {
+    #[serde(rename = "_class")]
+    class: Option<String>,
+    #[serde(default)]
+    jobs: Vec<JobTreeNode>,
+    #[serde(flatten)]
+    job: ShortJob,
+}
+
+/// Helper to list jobs recursively
+#[derive(Debug)]
+pub struct JobWalker<'a> {
+    jenkins_client: &'a Jenkins,
+    max_depth: usize,
+    classes: Vec<String>,
+    name_globs: Vec<Regex>,
+}
+
+impl<'a> JobWalker<'a> {
+    pub(crate) fn new(jenkins_client: &'a Jenkins) -> Self {
+        JobWalker {
+            jenkins_client,
+            max_depth: DEFAULT_MAX_DEPTH,
+            classes: vec![],
+            name_globs: vec![],
+        }
+    }
+
+    /// Limit the number of levels of folders traversed. With a depth of 0, only
+    /// the top level jobs are listed
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Only list jobs of type `T`. Can be called several times to list jobs of
+    /// any of the types given
+    pub fn with_class<T: Class + Job>(self) -> Self {
+        self.with_class_name(T::with_class())
+    }
+
+    /// Only list jobs whose _class is `class`. Can be called several times to
+    /// list jobs of any of the classes given
+    pub fn with_class_name(mut self, class: &str) -> Self {
+        self.classes.push(class.to_string());
+        self
+    }
+
+    /// Only list jobs whose name match `glob`, where `*` matches any sequence of
+    /// characters and `?` matches any single character. If `glob` contains a `/`,
+    /// it is matched against the full name of the job, including its folders.
+    /// Can be called several times to list jobs matching any of the globs given
+    pub fn with_name_glob(mut self, glob: &str) -> Result<Self> {
+        self.name_globs.push(glob_to_regex(glob)?);
+        Ok(self)
+    }
+
+    /// List the jobs
+    pub fn walk(self) -> Result<JobWalk> {
+        let tree: JobTree = self
+            .jenkins_client
+            .get_with_params(
+                &Path::Home,
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                    TreeBuilder::new()
+                        .with_field(jobs_tree(self.max_depth))
+                        .build(),
+                )),
+            )?
+            .json()?;
+
+        let mut jobs = vec![];
+        self.flatten(tree.jobs, &[], &mut jobs);
+        Ok(JobWalk {
+            jobs: jobs.into_iter(),
+        })
+    }
+
+    fn flatten(&self, nodes: Vec<JobTreeNode>, folders: &[String], jobs: &mut Vec<WalkedJob>) {
+        for node in nodes {
+            let walked = WalkedJob {
+                class: node.class,
+                folders: folders.to_vec(),
+                job: node.job,
+            };
+            let mut sub_folders = folders.to_vec();
+            sub_folders.push(walked.job.name.clone());
+            if self.matches(&walked) {
+                jobs.push(walked);
+            }
+            self.flatten(node.jobs, &sub_folders, jobs);
+        }
+    }
+
+    fn matches(&self, job: &WalkedJob) -> bool {
+        let class_matches = self.classes.is_empty()
+            || job
+                .class
+                .as_ref()
+                .map(|class| self.classes.contains(class))
+                .unwrap_or(false);
+        let name_matches = self.name_globs.is_empty()
+            || self.name_globs.iter().any(|glob| {
+                if glob.as_str().contains('/') {
+                    glob.is_match(&job.full_name())
+                } else {
+                    glob.is_match(&job.job.name)
+                }
+            });
+        class_matches && name_matches
+    }
+}
+
+/// Iterator over the jobs found by a `JobWalker`
+#[derive(Debug)]
+pub struct JobWalk {
+    jobs: std::vec::IntoIter<WalkedJob>,
+}
+
+impl Iterator for JobWalk {
+    type Item = WalkedJob;
+
+    fn next(&mut self) -> Option<WalkedJob> {
+        self.jobs.next()
+    }
+}
+
+fn jobs_tree(levels: usize) -> TreeBuilder {
+    let tree = TreeBuilder::object("jobs")
+        .with_subfield("_class")
+        .with_subfield("name")
+        .with_subfield("url")
+        .with_subfield("color");
+    if levels > 0 {
+        tree.with_subfield(jobs_tree(levels - 1))
+    } else {
+        tree
+    }
+}
+
+/// Names of a job and the folders containing it, from its URL like `/job/folder/job/name/`
+pub(crate) fn names_from_url(url: &str) -> Vec<String> {
+    let mut segments = url.split('/');
+    let mut names = vec![];
+    while let Some(segment) = segments.next() {
+        if segment == "job" {
+            if let Some(name) = segments.next() {
+                names.push(urlencoding::decode(name).unwrap_or_else(|_| name.to_string()));
+            }
+        }
+    }
+    names
+}
+
+fn glob_to_regex(glob: &str) -> Result<Regex> {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Ok(Regex::new(&pattern)?)
+}
+
+impl Jenkins {
+    /// Create a `JobWalker` to list jobs recursively through folders, with filters
+    pub fn job_walker(&self) -> JobWalker<'_> {
+        JobWalker::new(self)
+    }
+
+    /// List every job recursively through folders, up to
+    /// [`DEFAULT_MAX_DEPTH`](job/walker/constant.DEFAULT_MAX_DEPTH.html) levels of folders
+    pub fn walk_jobs(&self) -> Result<JobWalk> {
+        self.job_walker().walk()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn can_get_names_from_url() {
+        assert_eq!(
+            super::names_from_url("http://localhost:8080/job/my%20team/job/app/"),
+            vec!["my team".to_string(), "app".to_string()]
+        );
+        assert!(super::names_from_url("http://localhost:8080/view/all/").is_empty());
+    }
+
+    #[test]
+    fn can_match_globs() {
+        let glob = super::glob_to_regex("team-?-*").unwrap();
+        assert!(glob.is_match("team-a-build"));
+        assert!(glob.is_match("team-b-"));
+        assert!(!glob.is_match("team-ab-build"));
+        assert!(!glob.is_match("my-team-a-build"));
+
+        let glob = super::glob_to_regex("folder/job.name").unwrap();
+        assert!(glob.is_match("folder/job.name"));
+        assert!(!glob.is_match("folder/jobxname"));
+    }
+
+    #[test]
+    fn can_build_tree_with_depth() {
+        assert_eq!(
+            super::jobs_tree(1).build().to_string(),
+            "jobs[_class,name,url,color,jobs[_class,name,url,color]]"
+        );
+    }
+
+    #[test]
+    fn can_walk_jobs_in_folders() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/api/json\?tree=jobs".to_string()),
+        )
+        .with_body(
+            r#"{"jobs": [
+                {"_class": "hudson.model.FreeStyleProject", "name": "top", "url": "/job/top/"},
+                {"_class": "com.cloudbees.hudson.plugins.folder.Folder", "name": "folder", "url": "/job/folder/", "jobs": [
+                    {"_class": "org.jenkinsci.plugins.workflow.job.WorkflowJob", "name": "pipeline", "url": "/job/folder/job/pipeline/"}
+                ]}
+            ]}"#,
+        )
+        .create();
+
+        let jobs = jenkins_client
+            .walk_jobs()
+            .unwrap()
+            .map(|job| job.full_name())
+            .collect::<Vec<_>>();
+        assert_eq!(jobs, vec!["top", "folder", "folder/pipeline"]);
+
+        let jobs = jenkins_client
+            .job_walker()
+            .with_class::<crate::job::WorkflowJob>()
+            .walk()
+            .unwrap()
+            .map(|job| job.full_name())
+            .collect::<Vec<_>>();
+        assert_eq!(jobs, vec!["folder/pipeline"]);
+
+        let jobs = jenkins_client
+            .job_walker()
+            .with_name_glob("fold*")
+            .unwrap()
+            .walk()
+            .unwrap()
+            .map(|job| job.full_name())
+            .collect::<Vec<_>>();
+        assert_eq!(jobs, vec!["folder"]);
+    }
+}
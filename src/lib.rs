@@ -52,11 +52,21 @@ pub mod helpers;
 pub mod action;
 pub mod build;
 pub mod changeset;
+pub mod credentials;
+pub mod export;
 pub mod home;
+pub mod jenkinsfile;
 pub mod job;
 pub mod nodes;
+pub mod permission;
+pub mod plugin;
 pub mod property;
 pub mod queue;
 pub mod scm;
+mod script;
+pub mod server;
+pub mod sync;
 pub mod user;
 pub mod view;
+
+mod xml;
@@ -0,0 +1,301 @@
+//! Helper to create a permanent agent
+
+use serde_json::json;
+
+use crate::client::Result;
+use crate::client_internals::Path;
+use crate::home::Mode;
+use crate::Jenkins;
+
+/// How Jenkins starts an agent
+#[derive(Debug, Clone, Copy)]
+pub enum Launcher<'a> {
+    /// The agent connects to Jenkins through JNLP
+    Jnlp {
+        /// Directory used by the agent for its internal data, relative to the remote root
+        /// directory. Disabled if `None`
+        work_dir: Option<&'a str>,
+    },
+    /// Jenkins connects to the agent through SSH, from the SSH Build Agents plugin
+    Ssh {
+        /// Host of the agent
+        host: &'a str,
+        /// SSH port of the agent
+        port: u16,
+        /// ID of the credentials used to connect
+        credentials_id: &'a str,
+    },
+    /// Jenkins starts the agent by running a command on the master
+    Command {
+        /// Command to run
+        command: &'a str,
+    },
+}
+
+/// How Jenkins verifies the host key of an agent started through SSH
+#[derive(Debug, Clone, Copy)]
+pub enum HostKeyVerification<'a> {
+    /// Check the host key against the `known_hosts` file of the user running Jenkins
+    KnownHosts,
+    /// Trust the host key seen on the first connection, after it has been approved by
+    /// an administrator if `require_initial_manual_trust` is set
+    ManuallyTrusted {
+        /// Require an administrator to approve the host key of the first connection
+        require_initial_manual_trust: bool,
+    },
+    /// Check the host key against a provided key
+    ManuallyProvided {
+        /// Host key, like `ssh-ed25519 AAAA...`
+        key: &'a str,
+    },
+    /// Accept any host key. This is insecure, as anyone able to intercept the connection
+    /// can impersonate the agent
+    NonVerifying,
+}
+
+impl<'a> HostKeyVerification<'a> {
+    fn to_json(self) -> serde_json::Value {
+        let class = |name: &str| format!("hudson.plugins.sshslaves.verifiers.{}", name);
+        match self {
+            HostKeyVerification::KnownHosts => {
+                let class = class("KnownHostsFileKeyVerificationStrategy");
+                json!({ "stapler-class": class, "$class": class })
+            }
+            HostKeyVerification::ManuallyTrusted {
+                require_initial_manual_trust,
+            } => {
+                let class = class("ManuallyTrustedKeyVerificationStrategy");
+                json!({
+                    "stapler-class": class,
+                    "$class": class,
+                    "requireInitialManualTrust": require_initial_manual_trust,
+                })
+            }
+            HostKeyVerification::ManuallyProvided { key } => {
+                let class = class("ManuallyProvidedKeyVerificationStrategy");
+                json!({ "stapler-class": class, "$class": class, "key": key })
+            }
+            HostKeyVerification::NonVerifying => {
+                let class = class("NonVerifyingKeyVerificationStrategy");
+                json!({ "stapler-class": class, "$class": class })
+            }
+        }
+    }
+}
+
+impl<'a> Launcher<'a> {
+    fn to_json(self, host_key_verification: HostKeyVerification) -> serde_json::Value {
+        match self {
+            Launcher::Jnlp { work_dir } => json!({
+                "stapler-class": "hudson.slaves.JNLPLauncher",
+                "$class": "hudson.slaves.JNLPLauncher",
+                "workDirSettings": {
+                    "disabled": work_dir.is_none(),
+                    "workDirPath": "",
+                    "internalDir": work_dir.unwrap_or("remoting"),
+                    "failIfWorkDirIsMissing": false,
+                },
+                "tunnel": "",
+                "vmargs": "",
+            }),
+            Launcher::Ssh {
+                host,
+                port,
+                credentials_id,
+            } => json!({
+                "stapler-class": "hudson.plugins.sshslaves.SSHLauncher",
+                "$class": "hudson.plugins.sshslaves.SSHLauncher",
+                "host": host,
+                "port": port.to_string(),
+                "credentialsId": credentials_id,
+                "sshHostKeyVerificationStrategy": host_key_verification.to_json(),
+            }),
+            Launcher::Command { command } => json!({
+                "stapler-class": "hudson.slaves.CommandLauncher",
+                "$class": "hudson.slaves.CommandLauncher",
+                "command": command,
+            }),
+        }
+    }
+}
+
+/// Helper to create a permanent agent
+#[derive(Debug)]
+pub struct PermanentAgentBuilder<'a, 'b> {
+    name: &'a str,
+    jenkins_client: &'b Jenkins,
+    description: &'a str,
+    executors: u32,
+    remote_fs: &'a str,
+    labels: &'a str,
+    mode: Mode,
+    launcher: Launcher<'a>,
+    host_key_verification: HostKeyVerification<'a>,
+}
+
+impl<'a, 'b> PermanentAgentBuilder<'a, 'b> {
+    pub(crate) fn new(name: &'a str, jenkins_client: &'b Jenkins) -> Self {
+        PermanentAgentBuilder {
+            name,
+            jenkins_client,
+            description: "",
+            executors: 1,
+            remote_fs: "/home/jenkins",
+            labels: "",
+            mode: Mode::Normal,
+            launcher: Launcher::Jnlp { work_dir: None },
+            host_key_verification: HostKeyVerification::KnownHosts,
+        }
+    }
+
+    /// Set the description of the agent
+    pub fn with_description(mut self, description: &'a str) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Set the number of executors of the agent, default to 1
+    pub fn with_executors(mut self, executors: u32) -> Self {
+        self.executors = executors;
+        self
+    }
+
+    /// Set the remote root directory of the agent, default to `/home/jenkins`
+    pub fn with_remote_fs(mut self, remote_fs: &'a str) -> Self {
+        self.remote_fs = remote_fs;
+        self
+    }
+
+    /// Set the labels of the agent, separated by spaces
+    pub fn with_labels(mut self, labels: &'a str) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Set how jobs are allocated to the agent, default to `Mode::Normal`
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set how the agent is started, default to JNLP
+    pub fn with_launcher(mut self, launcher: Launcher<'a>) -> Self {
+        self.launcher = launcher;
+        self
+    }
+
+    /// Set how the host key of the agent is verified when started through SSH, default
+    /// to checking the `known_hosts` file of the user running Jenkins
+    pub fn with_host_key_verification(
+        mut self,
+        host_key_verification: HostKeyVerification<'a>,
+    ) -> Self {
+        self.host_key_verification = host_key_verification;
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "name": self.name,
+            "nodeDescription": self.description,
+            "numExecutors": self.executors.to_string(),
+            "remoteFS": self.remote_fs,
+            "labelString": self.labels,
+            "mode": self.mode,
+            "launcher": self.launcher.to_json(self.host_key_verification),
+            "retentionStrategy": {
+                "stapler-class": "hudson.slaves.RetentionStrategy$Always",
+                "$class": "hudson.slaves.RetentionStrategy$Always",
+            },
+            "nodeProperties": {
+                "stapler-class-bag": "true",
+            },
+            "type": "hudson.slaves.DumbSlave",
+        })
+    }
+
+    /// Create the agent
+    pub fn send(self) -> Result<()> {
+        let json = self.to_json().to_string();
+        let _ = self.jenkins_client.post_with_body(
+            &Path::CreateComputer,
+            serde_urlencoded::to_string([
+                ("name", self.name),
+                ("type", "hudson.slaves.DumbSlave"),
+                ("json", &json),
+            ])?,
+            &[],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_create_agent() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = mockito::mock("POST", "/computer/doCreateItem")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("name".to_string(), "agent".to_string()),
+                mockito::Matcher::UrlEncoded(
+                    "type".to_string(),
+                    "hudson.slaves.DumbSlave".to_string(),
+                ),
+            ]))
+            .create();
+
+        let builder = PermanentAgentBuilder::new("agent", &jenkins_client)
+            .with_executors(2)
+            .with_labels("linux docker")
+            .with_launcher(Launcher::Command {
+                command: "ssh agent java -jar agent.jar",
+            });
+        let json = builder.to_json();
+        assert_eq!(json["numExecutors"], "2");
+        assert_eq!(json["mode"], "NORMAL");
+        assert_eq!(json["launcher"]["$class"], "hudson.slaves.CommandLauncher");
+
+        assert!(builder.send().is_ok());
+        mock.assert();
+    }
+
+    #[test]
+    fn verifies_ssh_host_keys() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+        let launcher = Launcher::Ssh {
+            host: "agent.example.com",
+            port: 22,
+            credentials_id: "ssh-key",
+        };
+
+        let json = PermanentAgentBuilder::new("agent", &jenkins_client)
+            .with_launcher(launcher)
+            .to_json();
+        assert_eq!(
+            json["launcher"]["sshHostKeyVerificationStrategy"]["$class"],
+            "hudson.plugins.sshslaves.verifiers.KnownHostsFileKeyVerificationStrategy"
+        );
+
+        let json = PermanentAgentBuilder::new("agent", &jenkins_client)
+            .with_launcher(launcher)
+            .with_host_key_verification(HostKeyVerification::ManuallyProvided {
+                key: "ssh-ed25519 AAAAC3Nza",
+            })
+            .to_json();
+        let strategy = &json["launcher"]["sshHostKeyVerificationStrategy"];
+        assert_eq!(
+            strategy["$class"],
+            "hudson.plugins.sshslaves.verifiers.ManuallyProvidedKeyVerificationStrategy"
+        );
+        assert_eq!(strategy["key"], "ssh-ed25519 AAAAC3Nza");
+    }
+}
@@ -4,8 +4,11 @@ use std::collections::HashMap;
 
 use serde::{self, Deserialize, Serialize};
 
+use crate::client::Result;
 use crate::helpers::Class;
+use crate::Jenkins;
 
+use super::load::LoadStatistics;
 use super::monitor;
 
 /// Helper type to act on a `Computer`
@@ -22,6 +25,15 @@ impl<'a> From<&'a String> for ComputerName<'a> {
     }
 }
 
+/// Name of a computer in its URL, from its display name. The name of the master node
+/// depends on the version of Jenkins
+fn path_name<'a>(jenkins_client: &Jenkins, display_name: &'a str) -> Result<&'a str> {
+    match display_name {
+        "master" | "Built-In Node" => jenkins_client.master_node_name(),
+        name => Ok(name),
+    }
+}
+
 /// Trait implemented by specialization of computers
 pub trait Computer {}
 
@@ -78,7 +90,11 @@ macro_rules! computer_with_common_fields_and_impl {
             /// Labels assigned to the computer
             pub assigned_labels: Vec<AssignedLabel>,
 
-            // TODO: actions, assignedLabels, loadStatistics
+            /// Load statistics of the computer, only available when querying them explicitly
+            #[serde(default)]
+            pub load_statistics: LoadStatistics,
+
+            // TODO: actions
 
             $(
                 $(#[$field_attr])*
@@ -90,6 +106,19 @@ macro_rules! computer_with_common_fields_and_impl {
             )*)*
         }
         impl Computer for $name {}
+        impl $name {
+            /// Read the data of the core monitors
+            pub fn monitors(&self) -> Result<monitor::Monitors> {
+                Ok(serde_json::from_value(serde_json::to_value(&self.monitor_data)?)?)
+            }
+
+            /// Run a Groovy script on this computer through its script console, and get
+            /// its output. The script console must be enabled with
+            /// `JenkinsBuilder::enable_script_console`
+            pub fn run_groovy(&self, jenkins_client: &Jenkins, script: &str) -> Result<String> {
+                jenkins_client.run_groovy_on_node(path_name(jenkins_client, &self.display_name)?, script)
+            }
+        }
     };
 }
 
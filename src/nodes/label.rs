@@ -0,0 +1,403 @@
+//! Types to parse a `Label`, and to evaluate label expressions
+
+use serde::{self, Deserialize, Serialize};
+
+use super::computer::{AssignedLabel, CommonComputer};
+use super::load::{load_statistics_tree, LoadStatistics};
+use super::ComputerSet;
+use crate::client::{self, AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, Name, Path};
+use crate::home::Mode;
+use crate::job::ShortJob;
+use crate::Jenkins;
+
+/// Helper type to act on a `Label`, from a label name or a label expression
+#[derive(Debug)]
+pub struct LabelName<'a>(pub &'a str);
+impl<'a> From<&'a str> for LabelName<'a> {
+    fn from(v: &'a str) -> LabelName<'a> {
+        LabelName(v)
+    }
+}
+impl<'a> From<&'a String> for LabelName<'a> {
+    fn from(v: &'a String) -> LabelName<'a> {
+        LabelName(v)
+    }
+}
+
+/// Short Node that is used in lists and links from other structs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortNode {
+    /// _class provided by Jenkins
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    /// Name of the node, empty for the master
+    pub node_name: String,
+    /// Description of the node
+    pub node_description: Option<String>,
+    /// Number of executors of the node
+    pub num_executors: u32,
+    /// Mode of the node for job selections
+    pub mode: Option<Mode>,
+
+    #[cfg(not(feature = "extra-fields-visibility"))]
+    #[serde(flatten)]
+    pub(crate) extra_fields: Option<serde_json::Value>,
+    #[cfg(feature = "extra-fields-visibility")]
+    /// Extra fields not parsed for a common object
+    #[serde(flatten)]
+    pub extra_fields: Option<serde_json::Value>,
+}
+
+impl ShortNode {
+    /// Get the `Computer` of this node
+    pub fn get_full_node(&self, jenkins_client: &Jenkins) -> Result<CommonComputer> {
+        if self.node_name.is_empty() {
+            jenkins_client.get_node(jenkins_client.master_node_name()?)
+        } else {
+            jenkins_client.get_node(&self.node_name)
+        }
+    }
+}
+
+/// A `Label`, grouping nodes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Label {
+    /// Name of the label, or the label expression
+    pub name: String,
+    /// Description of the label
+    pub description: Option<String>,
+    /// Number of busy executors
+    pub busy_executors: u32,
+    /// Number of idle executors
+    pub idle_executors: u32,
+    /// Number of executors
+    pub total_executors: u32,
+    /// Are all nodes of this label offline
+    pub offline: bool,
+    /// Nodes matching this label
+    pub nodes: Vec<ShortNode>,
+    /// Jobs tied to this label
+    pub tied_jobs: Vec<ShortJob>,
+    /// Load statistics of this label
+    #[serde(default)]
+    pub load_statistics: LoadStatistics,
+}
+
+impl AssignedLabel {
+    /// Get the full details of a `Label` matching the `AssignedLabel`
+    pub fn get_full_label(&self, jenkins_client: &Jenkins) -> Result<Label> {
+        jenkins_client.get_label(&self.name)
+    }
+}
+
+/// A label expression, like `linux && (docker || podman)`
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelExpression {
+    /// A label
+    Atom(String),
+    /// Negation of an expression, `!a`
+    Not(Box<LabelExpression>),
+    /// Both expressions, `a && b`
+    And(Box<LabelExpression>, Box<LabelExpression>),
+    /// Any of the expressions, `a || b`
+    Or(Box<LabelExpression>, Box<LabelExpression>),
+    /// If the first expression, then the second, `a -> b`
+    Implies(Box<LabelExpression>, Box<LabelExpression>),
+    /// Both expressions or none, `a <-> b`
+    Iff(Box<LabelExpression>, Box<LabelExpression>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Atom(String),
+    Not,
+    And,
+    Or,
+    Implies,
+    Iff,
+    LeftParenthesis,
+    RightParenthesis,
+}
+
+fn tokenize(expression: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::LeftParenthesis),
+            ')' => tokens.push(Token::RightParenthesis),
+            '!' => tokens.push(Token::Not),
+            '&' if chars.next_if_eq(&'&').is_some() => tokens.push(Token::And),
+            '|' if chars.next_if_eq(&'|').is_some() => tokens.push(Token::Or),
+            '-' if chars.next_if_eq(&'>').is_some() => tokens.push(Token::Implies),
+            '<' if chars.next_if_eq(&'-').is_some() && chars.next_if_eq(&'>').is_some() => {
+                tokens.push(Token::Iff)
+            }
+            '"' => {
+                let mut atom = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => atom.extend(chars.next()),
+                        Some(c) => atom.push(c),
+                        None => return Err("unterminated quoted label".to_string()),
+                    }
+                }
+                tokens.push(Token::Atom(atom));
+            }
+            '&' | '|' | '<' => return Err(format!("unexpected character '{}'", c)),
+            c => {
+                let mut atom = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "()!&|<\"".contains(next) {
+                        break;
+                    }
+                    if next == '-' && atom.ends_with('-') {
+                        break;
+                    }
+                    let _ = chars.next();
+                    if next == '>' && atom.ends_with('-') {
+                        let _ = atom.pop();
+                        tokens.push(Token::Atom(atom));
+                        atom = String::new();
+                        tokens.push(Token::Implies);
+                        break;
+                    }
+                    atom.push(next);
+                }
+                if !atom.is_empty() {
+                    tokens.push(Token::Atom(atom));
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn iff(&mut self) -> std::result::Result<LabelExpression, String> {
+        let mut left = self.implies()?;
+        while self.peek() == Some(&Token::Iff) {
+            let _ = self.next();
+            left = LabelExpression::Iff(Box::new(left), Box::new(self.implies()?));
+        }
+        Ok(left)
+    }
+
+    fn implies(&mut self) -> std::result::Result<LabelExpression, String> {
+        let mut left = self.or()?;
+        while self.peek() == Some(&Token::Implies) {
+            let _ = self.next();
+            left = LabelExpression::Implies(Box::new(left), Box::new(self.or()?));
+        }
+        Ok(left)
+    }
+
+    fn or(&mut self) -> std::result::Result<LabelExpression, String> {
+        let mut left = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            let _ = self.next();
+            left = LabelExpression::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> std::result::Result<LabelExpression, String> {
+        let mut left = self.not()?;
+        while self.peek() == Some(&Token::And) {
+            let _ = self.next();
+            left = LabelExpression::And(Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> std::result::Result<LabelExpression, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(LabelExpression::Not(Box::new(self.not()?))),
+            Some(Token::LeftParenthesis) => {
+                let expression = self.iff()?;
+                match self.next() {
+                    Some(Token::RightParenthesis) => Ok(expression),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(Token::Atom(atom)) => Ok(LabelExpression::Atom(atom)),
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+impl LabelExpression {
+    /// Parse a label expression
+    pub fn parse(expression: &str) -> Result<LabelExpression> {
+        let error = |message| client::Error::InvalidLabelExpression {
+            expression: expression.to_string(),
+            message,
+        };
+        let mut parser = Parser {
+            tokens: tokenize(expression).map_err(error)?,
+            position: 0,
+        };
+        let parsed = parser.iff().map_err(error)?;
+        match parser.next() {
+            None => Ok(parsed),
+            Some(token) => Err(error(format!("unexpected {:?}", token)).into()),
+        }
+    }
+
+    /// Does this expression match a node with the given labels
+    pub fn matches<'a, I>(&self, labels: I) -> bool
+    where
+        I: IntoIterator<Item = &'a str> + Clone,
+    {
+        match self {
+            LabelExpression::Atom(atom) => labels.into_iter().any(|label| label == atom),
+            LabelExpression::Not(expression) => !expression.matches(labels),
+            LabelExpression::And(left, right) => {
+                left.matches(labels.clone()) && right.matches(labels)
+            }
+            LabelExpression::Or(left, right) => {
+                left.matches(labels.clone()) || right.matches(labels)
+            }
+            LabelExpression::Implies(left, right) => {
+                !left.matches(labels.clone()) || right.matches(labels)
+            }
+            LabelExpression::Iff(left, right) => {
+                left.matches(labels.clone()) == right.matches(labels)
+            }
+        }
+    }
+
+    /// Does this expression match a node with the given `AssignedLabel`s
+    pub fn matches_labels(&self, labels: &[AssignedLabel]) -> bool {
+        self.matches(labels.iter().map(|label| label.name.as_str()))
+    }
+}
+
+impl ComputerSet {
+    /// List the computers matching a label expression
+    pub fn computers_matching(&self, expression: &LabelExpression) -> Vec<&CommonComputer> {
+        self.computers
+            .iter()
+            .filter(|computer| expression.matches_labels(&computer.assigned_labels))
+            .collect()
+    }
+}
+
+impl Jenkins {
+    /// Get a `Label`, from a label name or a label expression
+    pub fn get_label<'a, L>(&self, label: L) -> Result<Label>
+    where
+        L: Into<LabelName<'a>>,
+    {
+        Ok(self
+            .get_with_params(
+                &Path::Label {
+                    name: Name::Name(label.into().0),
+                },
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                    TreeBuilder::new()
+                        .with_field("name")
+                        .with_field("description")
+                        .with_field("busyExecutors")
+                        .with_field("idleExecutors")
+                        .with_field("totalExecutors")
+                        .with_field("offline")
+                        .with_field(
+                            TreeBuilder::object("nodes")
+                                .with_subfield("_class")
+                                .with_subfield("nodeName")
+                                .with_subfield("nodeDescription")
+                                .with_subfield("numExecutors")
+                                .with_subfield("mode"),
+                        )
+                        .with_field(
+                            TreeBuilder::object("tiedJobs")
+                                .with_subfield("name")
+                                .with_subfield("url")
+                                .with_subfield("color"),
+                        )
+                        .with_field(load_statistics_tree("loadStatistics"))
+                        .build(),
+                )),
+            )?
+            .json()?)
+    }
+
+    /// List the computers whose labels match a label expression, where a job with this
+    /// label expression could run
+    pub fn get_nodes_matching(&self, expression: &str) -> Result<Vec<CommonComputer>> {
+        let expression = LabelExpression::parse(expression)?;
+        let nodes = self.get_nodes()?;
+        Ok(nodes
+            .computers_matching(&expression)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LabelExpression;
+
+    #[test]
+    fn can_parse_label_expression() {
+        assert_eq!(
+            LabelExpression::parse("linux && !windows || \"mac os\"").unwrap(),
+            LabelExpression::Or(
+                Box::new(LabelExpression::And(
+                    Box::new(LabelExpression::Atom("linux".to_string())),
+                    Box::new(LabelExpression::Not(Box::new(LabelExpression::Atom(
+                        "windows".to_string()
+                    )))),
+                )),
+                Box::new(LabelExpression::Atom("mac os".to_string())),
+            )
+        );
+        assert_eq!(
+            LabelExpression::parse("ubuntu-20.04->docker").unwrap(),
+            LabelExpression::Implies(
+                Box::new(LabelExpression::Atom("ubuntu-20.04".to_string())),
+                Box::new(LabelExpression::Atom("docker".to_string())),
+            )
+        );
+        assert!(LabelExpression::parse("linux &&").is_err());
+        assert!(LabelExpression::parse("(linux").is_err());
+        assert!(LabelExpression::parse("linux docker").is_err());
+    }
+
+    #[test]
+    fn can_match_labels() {
+        let expression = LabelExpression::parse("linux && (docker || podman)").unwrap();
+        assert!(expression.matches(vec!["linux", "docker"]));
+        assert!(expression.matches(vec!["podman", "linux", "x86"]));
+        assert!(!expression.matches(vec!["linux"]));
+        assert!(!expression.matches(vec!["windows", "docker"]));
+
+        let expression = LabelExpression::parse("gpu <-> cuda").unwrap();
+        assert!(expression.matches(vec!["gpu", "cuda"]));
+        assert!(expression.matches(vec!["linux"]));
+        assert!(!expression.matches(vec!["gpu"]));
+    }
+}
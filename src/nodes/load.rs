@@ -0,0 +1,287 @@
+//! Types to parse the load statistics of Jenkins, labels and computers
+
+use serde::{self, Deserialize, Serialize};
+
+use super::computer::ComputerName;
+use crate::client::{AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, Name, Path};
+use crate::Jenkins;
+
+/// A time series, with its latest value and its history, most recent first
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSeries {
+    /// History of the values, most recent first
+    #[serde(default)]
+    pub history: Vec<f32>,
+    /// Latest value
+    #[serde(default)]
+    pub latest: f32,
+}
+
+/// Timescale of a time series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Timescale {
+    /// A sample every 10 seconds
+    Sec10,
+    /// A sample every minute
+    Min,
+    /// A sample every hour
+    Hour,
+}
+
+impl Timescale {
+    fn field(self) -> &'static str {
+        match self {
+            Timescale::Sec10 => "sec10",
+            Timescale::Min => "min",
+            Timescale::Hour => "hour",
+        }
+    }
+
+    /// Number of seconds between two samples
+    pub fn seconds(self) -> u64 {
+        match self {
+            Timescale::Sec10 => 10,
+            Timescale::Min => 60,
+            Timescale::Hour => 3600,
+        }
+    }
+}
+
+/// A time series at several timescales
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiStageTimeSeries {
+    /// Values every 10 seconds
+    pub sec10: Option<TimeSeries>,
+    /// Values every minute
+    pub min: Option<TimeSeries>,
+    /// Values every hour
+    pub hour: Option<TimeSeries>,
+}
+
+impl MultiStageTimeSeries {
+    /// Get the time series at `timescale`
+    pub fn get(&self, timescale: Timescale) -> Option<&TimeSeries> {
+        match timescale {
+            Timescale::Sec10 => self.sec10.as_ref(),
+            Timescale::Min => self.min.as_ref(),
+            Timescale::Hour => self.hour.as_ref(),
+        }
+    }
+}
+
+/// Load statistics, as time series of executors and queue length
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadStatistics {
+    /// Number of executors available to build
+    pub available_executors: Option<MultiStageTimeSeries>,
+    /// Number of executors busy building
+    pub busy_executors: Option<MultiStageTimeSeries>,
+    /// Number of executors connecting
+    pub connecting_executors: Option<MultiStageTimeSeries>,
+    /// Number of executors defined
+    pub defined_executors: Option<MultiStageTimeSeries>,
+    /// Number of executors idle
+    pub idle_executors: Option<MultiStageTimeSeries>,
+    /// Number of executors online
+    pub online_executors: Option<MultiStageTimeSeries>,
+    /// Number of items waiting in the queue
+    pub queue_length: Option<MultiStageTimeSeries>,
+    /// Total number of executors
+    pub total_executors: Option<MultiStageTimeSeries>,
+}
+
+const SERIES: [&str; 8] = [
+    "availableExecutors",
+    "busyExecutors",
+    "connectingExecutors",
+    "definedExecutors",
+    "idleExecutors",
+    "onlineExecutors",
+    "queueLength",
+    "totalExecutors",
+];
+
+const TIMESCALES: [Timescale; 3] = [Timescale::Sec10, Timescale::Min, Timescale::Hour];
+
+impl LoadStatistics {
+    fn series(&self) -> [(&'static str, &Option<MultiStageTimeSeries>); 8] {
+        [
+            ("available_executors", &self.available_executors),
+            ("busy_executors", &self.busy_executors),
+            ("connecting_executors", &self.connecting_executors),
+            ("defined_executors", &self.defined_executors),
+            ("idle_executors", &self.idle_executors),
+            ("online_executors", &self.online_executors),
+            ("queue_length", &self.queue_length),
+            ("total_executors", &self.total_executors),
+        ]
+    }
+
+    /// Export the series available at `timescale` as CSV, with one column per series and
+    /// one row per sample, the most recent first. The first column is the age of the
+    /// sample in seconds
+    pub fn to_csv(&self, timescale: Timescale) -> String {
+        let columns = self
+            .series()
+            .iter()
+            .filter_map(|(name, series)| {
+                series
+                    .as_ref()
+                    .and_then(|series| series.get(timescale))
+                    .map(|series| (*name, &series.history))
+            })
+            .collect::<Vec<_>>();
+
+        let mut csv = std::iter::once("seconds_ago")
+            .chain(columns.iter().map(|(name, _)| *name))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push('\n');
+        let rows = columns
+            .iter()
+            .map(|(_, history)| history.len())
+            .max()
+            .unwrap_or(0);
+        for row in 0..rows {
+            let line = std::iter::once((row as u64 * timescale.seconds()).to_string())
+                .chain(columns.iter().map(|(_, history)| {
+                    history
+                        .get(row)
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                }))
+                .collect::<Vec<_>>()
+                .join(",");
+            csv.push_str(&line);
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Add the fields of `LoadStatistics` to a tree query parameter
+pub(crate) fn with_load_statistics(tree: TreeBuilder) -> TreeBuilder {
+    SERIES.iter().fold(tree, |tree, series| {
+        tree.with_field(
+            TIMESCALES
+                .iter()
+                .fold(TreeBuilder::object(series), |tree, timescale| {
+                    tree.with_subfield(
+                        TreeBuilder::object(timescale.field())
+                            .with_subfield("history")
+                            .with_subfield("latest"),
+                    )
+                }),
+        )
+    })
+}
+
+/// Tree query parameter to get the `LoadStatistics` in field `name`
+pub(crate) fn load_statistics_tree(name: &str) -> TreeBuilder {
+    with_load_statistics(TreeBuilder::object(name))
+}
+
+impl Jenkins {
+    /// Get the overall `LoadStatistics` of Jenkins
+    pub fn get_overall_load(&self) -> Result<LoadStatistics> {
+        Ok(self
+            .get_with_params(
+                &Path::OverallLoad,
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                    with_load_statistics(TreeBuilder::new()).build(),
+                )),
+            )?
+            .json()?)
+    }
+
+    /// Get the `LoadStatistics` of a `Computer`
+    pub fn get_node_load_statistics<'a, C>(&self, computer_name: C) -> Result<LoadStatistics>
+    where
+        C: Into<ComputerName<'a>>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct WithLoadStatistics {
+            load_statistics: LoadStatistics,
+        }
+
+        Ok(self
+            .get_with_params(
+                &Path::Computer {
+                    name: Name::Name(computer_name.into().0),
+                },
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                    TreeBuilder::new()
+                        .with_field(load_statistics_tree("loadStatistics"))
+                        .build(),
+                )),
+            )?
+            .json::<WithLoadStatistics>()?
+            .load_statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static LOAD: &str = r#"{
+        "busyExecutors": {
+            "sec10": {"history": [1.0, 0.5], "latest": 1.0},
+            "min": {"history": [0.75], "latest": 0.75}
+        },
+        "queueLength": {
+            "sec10": {"history": [2.0], "latest": 2.0}
+        },
+        "totalExecutors": {
+            "sec10": {"history": [2.0, 2.0], "latest": 2.0}
+        }
+    }"#;
+
+    #[test]
+    fn can_export_as_csv() {
+        let load: LoadStatistics = serde_json::from_str(LOAD).unwrap();
+        assert_eq!(
+            load.to_csv(Timescale::Sec10),
+            "seconds_ago,busy_executors,queue_length,total_executors
+0,1,2,2
+10,0.5,,2
+"
+        );
+        assert_eq!(
+            load.to_csv(Timescale::Min),
+            "seconds_ago,busy_executors
+0,0.75
+"
+        );
+        assert_eq!(load.to_csv(Timescale::Hour), "seconds_ago\n");
+    }
+
+    #[test]
+    fn can_get_overall_load() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/overallLoad/api/json\?tree=".to_string()),
+        )
+        .with_body(LOAD)
+        .create();
+
+        let load = jenkins_client.get_overall_load().unwrap();
+        assert_eq!(
+            load.busy_executors
+                .as_ref()
+                .and_then(|series| series.get(Timescale::Sec10))
+                .map(|series| series.latest),
+            Some(1.0)
+        );
+        assert!(load.available_executors.is_none());
+    }
+}
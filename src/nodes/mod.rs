@@ -2,10 +2,16 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::client;
 use crate::client_internals::{Name, Path, Result};
+use crate::xml;
 use crate::Jenkins;
 
+pub mod builder;
+use self::builder::PermanentAgentBuilder;
 pub mod computer;
+pub mod label;
+pub mod load;
 pub mod monitor;
 
 /// List of `Computer` associated to the `Jenkins` instance
@@ -23,6 +29,37 @@ pub struct ComputerSet {
     pub computers: Vec<computer::CommonComputer>,
 }
 
+/// Health of a `Computer`, evaluated from its monitors data
+#[derive(Debug, Clone)]
+pub struct ComputerHealth {
+    /// Name of the computer
+    pub name: String,
+    /// Problems found, empty if the computer is healthy
+    pub issues: Vec<monitor::HealthIssue>,
+}
+
+impl ComputerHealth {
+    /// Is the computer healthy
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl ComputerSet {
+    /// Evaluate the health of each computer against `thresholds`
+    pub fn health(&self, thresholds: &monitor::HealthThresholds) -> Result<Vec<ComputerHealth>> {
+        self.computers
+            .iter()
+            .map(|computer| {
+                Ok(ComputerHealth {
+                    name: computer.display_name.clone(),
+                    issues: computer.monitors()?.health(thresholds),
+                })
+            })
+            .collect()
+    }
+}
+
 impl Jenkins {
     /// Get a `ComputerSet`
     pub fn get_nodes(&self) -> Result<ComputerSet> {
@@ -45,8 +82,146 @@ impl Jenkins {
     pub fn get_master_node(&self) -> Result<computer::MasterComputer> {
         Ok(self
             .get(&Path::Computer {
-                name: Name::Name("(master)"),
+                name: Name::Name(self.master_node_name()?),
             })?
             .json()?)
     }
+
+    /// Create a `PermanentAgentBuilder` to setup a new permanent agent named `computer_name`
+    pub fn permanent_agent_builder<'a>(
+        &self,
+        computer_name: &'a str,
+    ) -> PermanentAgentBuilder<'a, '_> {
+        PermanentAgentBuilder::new(computer_name, self)
+    }
+
+    /// Delete a `Computer`
+    pub fn delete_node<'a, C>(&self, computer_name: C) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self.post(&Path::DeleteComputer {
+            name: Name::Name(computer_name.into().0),
+        })?;
+        Ok(())
+    }
+
+    /// Toggle a `Computer` temporarily offline, or back online, with an optional
+    /// `reason`
+    pub fn toggle_node_offline<'a, C>(&self, computer_name: C, reason: Option<&str>) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self.post_with_body(
+            &Path::ToggleOfflineComputer {
+                name: Name::Name(computer_name.into().0),
+            },
+            "",
+            &[("offlineMessage", reason.unwrap_or(""))],
+        )?;
+        Ok(())
+    }
+
+    /// Disconnect an agent, with an optional `reason`
+    pub fn disconnect_node<'a, C>(&self, computer_name: C, reason: Option<&str>) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self.post_with_body(
+            &Path::DisconnectComputer {
+                name: Name::Name(computer_name.into().0),
+            },
+            "",
+            &[("offlineMessage", reason.unwrap_or(""))],
+        )?;
+        Ok(())
+    }
+
+    /// Launch an agent that was disconnected
+    pub fn launch_node<'a, C>(&self, computer_name: C) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self.post(&Path::LaunchComputer {
+            name: Name::Name(computer_name.into().0),
+        })?;
+        Ok(())
+    }
+
+    /// Get the config.xml file of a `Computer`
+    pub fn get_node_config_xml<'a, C>(&self, computer_name: C) -> Result<String>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        Ok(self
+            .get_raw(&Path::ComputerConfigXML {
+                name: Name::Name(computer_name.into().0),
+            })?
+            .text()?)
+    }
+
+    /// Update the config.xml file of a `Computer`
+    pub fn update_node_config_xml<'a, C>(&self, computer_name: C, config: &str) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self.post_xml(
+            &Path::ComputerConfigXML {
+                name: Name::Name(computer_name.into().0),
+            },
+            config.to_string(),
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Get the secret used by an agent to connect through JNLP
+    pub fn get_node_secret<'a, C>(&self, computer_name: C) -> Result<String>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let jnlp = xml::Document::parse(
+            &self
+                .get_raw(&Path::ComputerJnlp {
+                    name: Name::Name(computer_name.into().0),
+                })?
+                .text()?,
+        )?;
+        jnlp.root
+            .child("application-desc")
+            .and_then(|application| application.child("argument"))
+            .map(xml::Element::text)
+            .ok_or_else(|| {
+                client::Error::UnexpectedResponse {
+                    missing: "secret in JNLP file".to_string(),
+                }
+                .into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn can_get_node_secret() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("GET", "/computer/my%20agent/slave-agent.jnlp")
+            .with_body(
+                r#"<jnlp codebase="http://localhost:8080/computer/my%20agent/" spec="1.0+">
+  <application-desc main-class="hudson.remoting.jnlp.Main">
+    <argument>0123456789abcdef</argument>
+    <argument>my agent</argument>
+  </application-desc>
+</jnlp>"#,
+            )
+            .create();
+
+        assert_eq!(
+            jenkins_client.get_node_secret("my agent").unwrap(),
+            "0123456789abcdef"
+        );
+    }
 }
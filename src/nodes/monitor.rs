@@ -1,5 +1,7 @@
 //! Types to parse the monitor data of a `Computer`
 
+use std::collections::HashMap;
+
 use serde::{self, Deserialize, Serialize};
 
 use crate::helpers::Class;
@@ -55,15 +57,15 @@ pub struct SwapSpaceMonitor {
 register_class!("hudson.node_monitors.SwapSpaceMonitor$MemoryUsage2" => SwapSpaceMonitor);
 impl MonitorData for SwapSpaceMonitor {}
 
-/// Swap Space Monitor
-#[derive(Deserialize, Debug)]
+/// Disk Space Monitor, used for the disk space and the temporary space
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DiskSpaceMonitorDescriptor {
     /// Timestamp
     pub timestamp: u64,
     /// Path monitored
     pub path: String,
-    /// Size used
+    /// Free space available
     pub size: u64,
 }
 register_class!("hudson.node_monitors.DiskSpaceMonitorDescriptor$DiskSpace" => DiskSpaceMonitorDescriptor);
@@ -90,3 +92,164 @@ pub struct ClockDifference {
 }
 register_class!("hudson.util.ClockDifference" => ClockDifference);
 impl MonitorData for ClockDifference {}
+
+/// Data of the core monitors of a `Computer`, keyed by monitor
+#[derive(Deserialize, Debug, Clone)]
+pub struct Monitors {
+    /// Operating system and architecture, from `hudson.node_monitors.ArchitectureMonitor`
+    #[serde(rename = "hudson.node_monitors.ArchitectureMonitor")]
+    pub architecture: Option<String>,
+    /// Clock difference with the master, from `hudson.node_monitors.ClockMonitor`
+    #[serde(rename = "hudson.node_monitors.ClockMonitor")]
+    pub clock: Option<ClockDifference>,
+    /// Free disk space in the remote root directory, from `hudson.node_monitors.DiskSpaceMonitor`
+    #[serde(rename = "hudson.node_monitors.DiskSpaceMonitor")]
+    pub disk_space: Option<DiskSpaceMonitorDescriptor>,
+    /// Free space in the temporary directory, from `hudson.node_monitors.TemporarySpaceMonitor`
+    #[serde(rename = "hudson.node_monitors.TemporarySpaceMonitor")]
+    pub temporary_space: Option<DiskSpaceMonitorDescriptor>,
+    /// Physical memory and swap space, from `hudson.node_monitors.SwapSpaceMonitor`
+    #[serde(rename = "hudson.node_monitors.SwapSpaceMonitor")]
+    pub swap_space: Option<SwapSpaceMonitor>,
+    /// Response time of the agent, from `hudson.node_monitors.ResponseTimeMonitor`
+    #[serde(rename = "hudson.node_monitors.ResponseTimeMonitor")]
+    pub response_time: Option<ResponseTimeMonitor>,
+    /// Data of the other monitors, like those provided by plugins
+    #[serde(flatten)]
+    pub others: HashMap<String, Data>,
+}
+
+/// Thresholds used to evaluate the health of a `Computer`
+#[derive(Debug, Clone, Copy)]
+pub struct HealthThresholds {
+    /// Minimum free disk space in the remote root directory, in bytes
+    pub disk_space: u64,
+    /// Minimum free space in the temporary directory, in bytes
+    pub temporary_space: u64,
+    /// Minimum available swap space, in bytes
+    pub swap_space: u64,
+}
+
+impl Default for HealthThresholds {
+    /// Default thresholds of Jenkins, 1GB of disk and temporary space, and no
+    /// threshold on the swap space
+    fn default() -> Self {
+        HealthThresholds {
+            disk_space: 1024 * 1024 * 1024,
+            temporary_space: 1024 * 1024 * 1024,
+            swap_space: 0,
+        }
+    }
+}
+
+/// A problem found when evaluating the health of a `Computer`
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthIssue {
+    /// Free disk space is below the threshold
+    LowDiskSpace {
+        /// Path monitored
+        path: String,
+        /// Free space, in bytes
+        available: u64,
+    },
+    /// Free temporary space is below the threshold
+    LowTemporarySpace {
+        /// Path monitored
+        path: String,
+        /// Free space, in bytes
+        available: u64,
+    },
+    /// Available swap space is below the threshold
+    LowSwapSpace {
+        /// Available swap space, in bytes
+        available: u64,
+    },
+}
+
+impl Monitors {
+    /// Evaluate the health of a computer from its monitors data. Monitors without data
+    /// are not considered
+    pub fn health(&self, thresholds: &HealthThresholds) -> Vec<HealthIssue> {
+        let mut issues = vec![];
+        if let Some(ref disk_space) = self.disk_space {
+            if disk_space.size < thresholds.disk_space {
+                issues.push(HealthIssue::LowDiskSpace {
+                    path: disk_space.path.clone(),
+                    available: disk_space.size,
+                });
+            }
+        }
+        if let Some(ref temporary_space) = self.temporary_space {
+            if temporary_space.size < thresholds.temporary_space {
+                issues.push(HealthIssue::LowTemporarySpace {
+                    path: temporary_space.path.clone(),
+                    available: temporary_space.size,
+                });
+            }
+        }
+        if let Some(swap_space) = self.swap_space {
+            if swap_space.available_swap_space < thresholds.swap_space {
+                issues.push(HealthIssue::LowSwapSpace {
+                    available: swap_space.available_swap_space,
+                });
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static MONITOR_DATA: &str = r#"{
+        "hudson.node_monitors.SwapSpaceMonitor": {
+            "_class": "hudson.node_monitors.SwapSpaceMonitor$MemoryUsage2",
+            "availablePhysicalMemory": 1000, "availableSwapSpace": 10,
+            "totalPhysicalMemory": 2000, "totalSwapSpace": 20
+        },
+        "hudson.node_monitors.TemporarySpaceMonitor": {
+            "_class": "hudson.node_monitors.DiskSpaceMonitorDescriptor$DiskSpace",
+            "timestamp": 1577836800000, "path": "/tmp", "size": 100
+        },
+        "hudson.node_monitors.DiskSpaceMonitor": {
+            "_class": "hudson.node_monitors.DiskSpaceMonitorDescriptor$DiskSpace",
+            "timestamp": 1577836800000, "path": "/home/jenkins", "size": 5000000000
+        },
+        "hudson.node_monitors.ArchitectureMonitor": "Linux (amd64)",
+        "hudson.node_monitors.ResponseTimeMonitor": null,
+        "hudson.node_monitors.ClockMonitor": {"_class": "hudson.util.ClockDifference", "diff": -3},
+        "hudson.plugin.versioncolumn.JVMVersionMonitor": "11.0.8"
+    }"#;
+
+    #[test]
+    fn can_read_monitors() {
+        let data: HashMap<String, Data> = serde_json::from_str(MONITOR_DATA).unwrap();
+        let monitors: Monitors =
+            serde_json::from_value(serde_json::to_value(data).unwrap()).unwrap();
+        assert_eq!(monitors.architecture, Some("Linux (amd64)".to_string()));
+        assert_eq!(monitors.clock.map(|clock| clock.diff), Some(-3));
+        assert!(monitors.response_time.is_none());
+        assert!(monitors
+            .others
+            .contains_key("hudson.plugin.versioncolumn.JVMVersionMonitor"));
+
+        assert_eq!(
+            monitors.health(&HealthThresholds::default()),
+            vec![HealthIssue::LowTemporarySpace {
+                path: "/tmp".to_string(),
+                available: 100
+            }]
+        );
+        assert_eq!(
+            monitors
+                .health(&HealthThresholds {
+                    swap_space: 100,
+                    temporary_space: 0,
+                    ..Default::default()
+                })
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Check the permissions of the current user before acting
+
+use std::fmt;
+
+use crate::client::{self, Result};
+use crate::client_internals::{Name, Path};
+use crate::Jenkins;
+
+/// A permission on an object that can be checked. Building, configuring and cancelling
+/// are not listed, as Jenkins only checks them on POST requests that act on the object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permission {
+    /// Delete the object
+    Delete,
+    /// Administer Jenkins
+    Administer,
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Permission::Delete => write!(f, "Delete"),
+            Permission::Administer => write!(f, "Administer"),
+        }
+    }
+}
+
+/// An object on which permissions are checked
+#[derive(Debug, Clone, Copy)]
+pub enum PermissionTarget<'a> {
+    /// Jenkins itself
+    Jenkins,
+    /// A job, from its full name with the names of the folders containing it separated
+    /// by `/`
+    Job(&'a str),
+    /// A folder, from its full name with the names of its parents separated by `/`
+    Folder(&'a str),
+    /// A computer
+    Computer(&'a str),
+}
+
+impl<'a> PermissionTarget<'a> {
+    /// Name of the permission in Jenkins, like `Job/Build`
+    pub fn permission_name(self, permission: Permission) -> String {
+        match (self, permission) {
+            (_, Permission::Administer) | (PermissionTarget::Jenkins, _) => {
+                format!("Overall/{}", permission)
+            }
+            (PermissionTarget::Computer(_), _) => format!("Agent/{}", permission),
+            _ => format!("Job/{}", permission),
+        }
+    }
+
+    /// Page that can only be read with the permission
+    fn page(self, permission: Permission) -> Option<Path<'a>> {
+        match (self, permission) {
+            (_, Permission::Administer) => Some(Path::Raw { path: "/script" }),
+            (PermissionTarget::Job(name), Permission::Delete)
+            | (PermissionTarget::Folder(name), Permission::Delete) => {
+                Some(Path::for_job(name, |name| Path::JobPage {
+                    name,
+                    page: "delete",
+                }))
+            }
+            (PermissionTarget::Computer(name), Permission::Delete) => Some(Path::ComputerPage {
+                name: Name::Name(name),
+                page: "delete",
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Jenkins {
+    /// Check if the current user has a permission on an object.
+    ///
+    /// Jenkins doesn't expose the permissions of a user, so this checks if the user can
+    /// read a page that is only shown with the permission. `Delete` can be checked on
+    /// jobs, folders and computers, and `Administer` on any object. Checking `Delete` on
+    /// Jenkins itself fails with `UnsupportedPermissionCheck`
+    pub fn has_permission(&self, target: PermissionTarget, permission: Permission) -> Result<bool> {
+        match target.page(permission) {
+            Some(path) => self.is_authorized(&path),
+            None => Err(client::Error::UnsupportedPermissionCheck {
+                permission: target.permission_name(permission),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_check_permission() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _allowed = mockito::mock("GET", "/job/folder/job/myjob/delete").create();
+        let _denied = mockito::mock("GET", "/computer/agent/delete")
+            .with_status(403)
+            .create();
+        let _post_only = mockito::mock("GET", "/script").with_status(405).create();
+
+        let target = PermissionTarget::Job("folder/myjob");
+        assert!(jenkins_client
+            .has_permission(target, Permission::Delete)
+            .unwrap());
+        assert!(!jenkins_client
+            .has_permission(PermissionTarget::Computer("agent"), Permission::Delete)
+            .unwrap());
+        // a page refusing GET requests doesn't tell if the user has the permission
+        assert!(jenkins_client
+            .has_permission(PermissionTarget::Jenkins, Permission::Administer)
+            .is_err());
+        match jenkins_client
+            .has_permission(PermissionTarget::Jenkins, Permission::Delete)
+            .unwrap_err()
+            .downcast_ref::<client::Error>()
+        {
+            Some(client::Error::UnsupportedPermissionCheck { .. }) => {}
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(target.permission_name(Permission::Delete), "Job/Delete");
+    }
+}
@@ -0,0 +1,315 @@
+//! Jenkins Plugins and Update Center
+
+use std::collections::BTreeMap;
+
+use serde::{self, Deserialize, Serialize};
+
+use crate::client::Result;
+use crate::client_internals::{Name, Path};
+use crate::xml;
+use crate::Jenkins;
+
+/// A dependency of a `Plugin`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDependency {
+    /// Short name of the plugin needed
+    pub short_name: String,
+    /// Minimum version of the plugin needed
+    pub version: String,
+    /// Is the dependency optional
+    pub optional: bool,
+}
+
+/// A `Plugin` installed on Jenkins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Plugin {
+    /// Short name of the plugin, used as its ID
+    pub short_name: String,
+    /// Display name of the plugin
+    pub long_name: String,
+    /// Version installed
+    pub version: String,
+    /// URL of the plugin website
+    pub url: Option<String>,
+    /// Is the plugin enabled
+    pub enabled: bool,
+    /// Is the plugin loaded. A plugin can be enabled but not active until a restart
+    pub active: bool,
+    /// Is an update available
+    pub has_update: bool,
+    /// Is the plugin pinned
+    #[serde(default)]
+    pub pinned: bool,
+    /// Is the plugin bundled with Jenkins
+    #[serde(default)]
+    pub bundled: bool,
+    /// Is the plugin deleted, waiting for a restart
+    #[serde(default)]
+    pub deleted: bool,
+    /// Can the plugin be downgraded to a previous version
+    #[serde(default)]
+    pub downgradable: bool,
+    /// Previous version, available for a downgrade
+    pub backup_version: Option<String>,
+    /// Minimum version of Jenkins needed
+    pub required_core_version: Option<String>,
+    /// Dependencies of the plugin
+    #[serde(default)]
+    pub dependencies: Vec<PluginDependency>,
+}
+
+/// List of `Plugin` installed on Jenkins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManager {
+    /// Plugins installed
+    pub plugins: Vec<Plugin>,
+}
+
+/// Difference of a plugin between two Jenkins instances
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginDrift {
+    /// Short name of the plugin
+    pub short_name: String,
+    /// Version on the first instance, `None` if it's not installed
+    pub left: Option<String>,
+    /// Version on the second instance, `None` if it's not installed
+    pub right: Option<String>,
+}
+
+impl PluginManager {
+    /// Get an installed plugin by its short name
+    pub fn get(&self, short_name: &str) -> Option<&Plugin> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.short_name == short_name)
+    }
+
+    /// List the plugins with an update available
+    pub fn with_updates(&self) -> Vec<&Plugin> {
+        self.plugins
+            .iter()
+            .filter(|plugin| plugin.has_update)
+            .collect()
+    }
+
+    /// Compare the plugins installed with those of another instance, listing the plugins
+    /// missing on one side or with a different version, sorted by short name
+    pub fn drift(&self, other: &PluginManager) -> Vec<PluginDrift> {
+        let mut plugins: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+        for plugin in &self.plugins {
+            plugins.entry(&plugin.short_name).or_default().0 = Some(&plugin.version);
+        }
+        for plugin in &other.plugins {
+            plugins.entry(&plugin.short_name).or_default().1 = Some(&plugin.version);
+        }
+        plugins
+            .into_iter()
+            .filter(|(_, (left, right))| left != right)
+            .map(|(short_name, (left, right))| PluginDrift {
+                short_name: short_name.to_string(),
+                left: left.map(ToString::to_string),
+                right: right.map(ToString::to_string),
+            })
+            .collect()
+    }
+}
+
+/// Status of an `UpdateCenterJob`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCenterJobStatus {
+    /// _class provided by Jenkins
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    /// Type of status, like `Pending`, `Installing`, `Success` or `Failure`
+    #[serde(rename = "type")]
+    pub status_type: String,
+    /// Did the job succeed
+    pub success: bool,
+}
+
+/// A job of the update center, like a plugin installation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCenterJob {
+    /// _class provided by Jenkins
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    /// ID of the job
+    pub id: u32,
+    /// Type of job, like `InstallationJob` or `ConnectionCheckJob`
+    #[serde(rename = "type")]
+    pub job_type: String,
+    /// Name of the plugin, for installation jobs
+    pub name: Option<String>,
+    /// Status of the job, for installation jobs
+    pub status: Option<UpdateCenterJobStatus>,
+    /// Error message, if the job failed
+    pub error_message: Option<String>,
+}
+
+/// An update site
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSite {
+    /// ID of the site
+    pub id: String,
+    /// URL of the site
+    pub url: String,
+    /// Does the site have updates available
+    #[serde(default)]
+    pub has_updates: bool,
+}
+
+/// The update center, installing plugins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCenter {
+    /// Jobs of the update center
+    pub jobs: Vec<UpdateCenterJob>,
+    /// Update sites
+    #[serde(default)]
+    pub sites: Vec<UpdateSite>,
+    /// Is a restart needed to complete the jobs
+    #[serde(default)]
+    pub restart_required_for_completion: bool,
+}
+
+impl UpdateCenter {
+    /// Are all installation jobs finished
+    pub fn is_idle(&self) -> bool {
+        self.jobs.iter().all(|job| match job.status {
+            Some(ref status) => {
+                status.status_type != "Pending" && status.status_type != "Installing"
+            }
+            None => true,
+        })
+    }
+}
+
+impl Jenkins {
+    /// Get the `Plugin`s installed, with their dependencies
+    pub fn get_plugins(&self) -> Result<PluginManager> {
+        Ok(self
+            .get_with_params(&Path::PluginManager, [("depth", "1")])?
+            .json()?)
+    }
+
+    /// Install a plugin. Installs the latest version if `version` is `None`
+    pub fn install_plugin(&self, short_name: &str, version: Option<&str>) -> Result<()> {
+        self.install_plugins(&[(short_name, version)])
+    }
+
+    /// Install plugins from their short names and versions, the latest version being used
+    /// if it's `None`. Installation is done in the background by the update center
+    pub fn install_plugins(&self, plugins: &[(&str, Option<&str>)]) -> Result<()> {
+        let mut request = xml::Element::new("jenkins");
+        for (short_name, version) in plugins {
+            let mut install = xml::Element::new("install");
+            install.attributes.push((
+                "plugin".to_string(),
+                xml::escape(&format!("{}@{}", short_name, version.unwrap_or("latest"))),
+            ));
+            request.push_child(install);
+        }
+        let _ = self.post_xml(&Path::InstallNecessaryPlugins, request.to_string(), &[])?;
+        Ok(())
+    }
+
+    /// Enable a plugin. It will be active after a restart
+    pub fn enable_plugin(&self, short_name: &str) -> Result<()> {
+        let _ = self.post(&Path::EnablePlugin {
+            name: Name::Name(short_name),
+        })?;
+        Ok(())
+    }
+
+    /// Disable a plugin. It will be inactive after a restart
+    pub fn disable_plugin(&self, short_name: &str) -> Result<()> {
+        let _ = self.post(&Path::DisablePlugin {
+            name: Name::Name(short_name),
+        })?;
+        Ok(())
+    }
+
+    /// Uninstall a plugin. It will be removed after a restart
+    pub fn uninstall_plugin(&self, short_name: &str) -> Result<()> {
+        let _ = self.post(&Path::UninstallPlugin {
+            name: Name::Name(short_name),
+        })?;
+        Ok(())
+    }
+
+    /// Get the `UpdateCenter`, with the status of its jobs
+    pub fn get_update_center(&self) -> Result<UpdateCenter> {
+        Ok(self
+            .get_with_params(&Path::UpdateCenter, [("depth", "1")])?
+            .json()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(short_name: &str, version: &str) -> Plugin {
+        serde_json::from_value(serde_json::json!({
+            "shortName": short_name,
+            "longName": short_name,
+            "version": version,
+            "enabled": true,
+            "active": true,
+            "hasUpdate": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn can_compute_drift() {
+        let left = PluginManager {
+            plugins: vec![plugin("git", "4.2.2"), plugin("matrix-auth", "2.6")],
+        };
+        let right = PluginManager {
+            plugins: vec![
+                plugin("git", "4.3.0"),
+                plugin("matrix-auth", "2.6"),
+                plugin("ws-cleanup", "0.38"),
+            ],
+        };
+        assert_eq!(
+            left.drift(&right),
+            vec![
+                PluginDrift {
+                    short_name: "git".to_string(),
+                    left: Some("4.2.2".to_string()),
+                    right: Some("4.3.0".to_string()),
+                },
+                PluginDrift {
+                    short_name: "ws-cleanup".to_string(),
+                    left: None,
+                    right: Some("0.38".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_install_plugins() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = mockito::mock("POST", "/pluginManager/installNecessaryPlugins")
+            .match_body(r#"<jenkins><install plugin="git@4.2.2"/><install plugin="ws-cleanup@latest"/></jenkins>"#)
+            .create();
+
+        assert!(jenkins_client
+            .install_plugins(&[("git", Some("4.2.2")), ("ws-cleanup", None)])
+            .is_ok());
+        mock.assert();
+    }
+}
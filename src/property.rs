@@ -46,3 +46,13 @@ impl Property for RateLimitBranchProperty {}
 pub struct BuildDiscarderProperty {}
 register_class!("jenkins.model.BuildDiscarderProperty" => BuildDiscarderProperty);
 impl Property for BuildDiscarderProperty {}
+
+/// Email address of a user
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MailerUserProperty {
+    /// Email address
+    pub address: Option<String>,
+}
+register_class!("hudson.tasks.Mailer$UserProperty" => MailerUserProperty);
+impl Property for MailerUserProperty {}
@@ -0,0 +1,155 @@
+//! Run Groovy scripts through the script console
+
+use log::warn;
+use regex::Regex;
+
+use crate::client::{self, Result};
+use crate::client_internals::{Name, Path};
+use crate::nodes::computer::ComputerName;
+use crate::Jenkins;
+
+/// Find an exception thrown by a script in its output, from the first line followed by
+/// a stack trace
+fn exception_in_output(output: &str) -> Option<client::Error> {
+    let re =
+        Regex::new(r"^((?:[a-zA-Z_$][\w$]*\.)+[\w$]*(?:Exception|Error))(?::\s*(.*))?$").unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    lines.windows(2).find_map(|window| {
+        if !window[1].trim_start().starts_with("at ") {
+            return None;
+        }
+        re.captures(window[0].trim()).map(|captures| {
+            warn!(
+                "script failed with: {}",
+                captures.get(0).map(|v| v.as_str()).unwrap_or("unspecified")
+            );
+            client::Error::ScriptException {
+                exception: captures
+                    .get(1)
+                    .map(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                message: captures
+                    .get(2)
+                    .map(|v| v.as_str())
+                    .unwrap_or("no message")
+                    .to_string(),
+                output: output.to_string(),
+            }
+        })
+    })
+}
+
+impl Jenkins {
+    fn run_groovy_at(&self, path: &Path, script: &str) -> Result<String> {
+        if !self.script_console_enabled {
+            return Err(client::Error::ScriptConsoleDisabled.into());
+        }
+        let output = self
+            .post_with_body(
+                path,
+                serde_urlencoded::to_string([("script", script)])?,
+                &[],
+            )?
+            .text()?;
+        match exception_in_output(&output) {
+            Some(error) => Err(error.into()),
+            None => Ok(output),
+        }
+    }
+
+    /// Run a Groovy script on the master through the script console, and get its output.
+    /// The script console must be enabled with `JenkinsBuilder::enable_script_console`
+    ///
+    /// ```rust
+    ///# extern crate jenkins_api;
+    ///#
+    ///# use jenkins_api::JenkinsBuilder;
+    ///#
+    ///# fn example_function() {
+    ///     let jenkins = JenkinsBuilder::new("http://localhost:8080")
+    ///         .with_user("user", Some("password"))
+    ///         .enable_script_console()
+    ///         .build()
+    ///         .unwrap();
+    ///     let version = jenkins.run_groovy("print(Jenkins.VERSION)").unwrap();
+    ///# }
+    /// ```
+    pub fn run_groovy(&self, script: &str) -> Result<String> {
+        self.run_groovy_at(&Path::ScriptText, script)
+    }
+
+    /// Run a Groovy script on a node through its script console, and get its output.
+    /// The script console must be enabled with `JenkinsBuilder::enable_script_console`
+    pub fn run_groovy_on_node<'a, C>(&self, computer_name: C, script: &str) -> Result<String>
+    where
+        C: Into<ComputerName<'a>>,
+    {
+        self.run_groovy_at(
+            &Path::ComputerScriptText {
+                name: Name::Name(computer_name.into().0),
+            },
+            script,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn can_run_script() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .enable_script_console()
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("POST", "/scriptText")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "script".to_string(),
+                "println(1 + 1)".to_string(),
+            ))
+            .with_body("2\n")
+            .create();
+
+        assert_eq!(jenkins_client.run_groovy("println(1 + 1)").unwrap(), "2\n");
+    }
+
+    #[test]
+    fn can_get_script_exception() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .enable_script_console()
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("POST", "/computer/agent/scriptText")
+            .with_body(
+                "groovy.lang.MissingPropertyException: No such property: foo for class: Script1
+\tat org.codehaus.groovy.runtime.ScriptBytecodeAdapter.unwrap(ScriptBytecodeAdapter.java:66)
+\tat Script1.run(Script1.groovy:1)
+",
+            )
+            .create();
+
+        let result = jenkins_client.run_groovy_on_node("agent", "foo");
+        match result.unwrap_err().downcast_ref::<crate::client::Error>() {
+            Some(crate::client::Error::ScriptException {
+                exception, message, ..
+            }) => {
+                assert_eq!(exception, "groovy.lang.MissingPropertyException");
+                assert_eq!(message, "No such property: foo for class: Script1");
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn can_not_run_script_when_disabled() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        assert!(jenkins_client.run_groovy("println(1 + 1)").is_err());
+    }
+}
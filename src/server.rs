@@ -0,0 +1,258 @@
+//! Informations about the Jenkins server, and the features supported by its version
+
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::blocking::Response;
+use serde::Deserialize;
+
+use crate::client::{self, Result};
+use crate::client_internals::Path;
+use crate::Jenkins;
+
+/// Version of Jenkins, like `2.263.1`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(Vec<u32>);
+
+impl Version {
+    /// Numeric components of the version
+    pub fn components(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for Version {
+    type Err = std::num::ParseIntError;
+
+    /// Parse a version, ignoring any qualifier after the numeric components, like in
+    /// `2.277-SNAPSHOT`
+    fn from_str(version: &str) -> std::result::Result<Self, Self::Err> {
+        let numeric = version
+            .split(|c: char| c != '.' && !c.is_ascii_digit())
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.');
+        Ok(Version(
+            numeric
+                .split('.')
+                .map(u32::from_str)
+                .collect::<std::result::Result<_, _>>()?,
+        ))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let components = self.0.iter().map(u32::to_string).collect::<Vec<_>>();
+        write!(f, "{}", components.join("."))
+    }
+}
+
+/// Features depending on the version of Jenkins
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feature {
+    /// The master node was renamed to built-in node, with URL `/computer/(built-in)/`
+    BuiltInNode,
+    /// A reason can be given when putting Jenkins in quiet mode
+    QuietDownReason,
+}
+
+impl Feature {
+    /// Minimum version of Jenkins supporting the feature
+    pub fn minimum_version(self) -> Version {
+        match self {
+            Feature::BuiltInNode => Version(vec![2, 307]),
+            Feature::QuietDownReason => Version(vec![2, 267]),
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Feature::BuiltInNode => write!(f, "built-in node"),
+            Feature::QuietDownReason => write!(f, "quiet down reason"),
+        }
+    }
+}
+
+/// Informations about the Jenkins server
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    /// Version of Jenkins, from the `X-Jenkins` header
+    pub version: Option<Version>,
+    /// Compatibility version of Hudson, from the `X-Hudson` header
+    pub hudson_version: Option<String>,
+    /// Public key identifying the instance, from the `X-Instance-Identity` header
+    pub instance_identity: Option<String>,
+    /// False if this instance is either UNSECURED or NO_AUTHENTICATION
+    pub use_security: bool,
+    /// Does this instance use crumbs for CSRF
+    pub use_crumbs: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Security {
+    use_security: bool,
+    use_crumbs: bool,
+}
+
+fn header(response: &Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+}
+
+impl Jenkins {
+    /// Get informations about the Jenkins server
+    pub fn server_info(&self) -> Result<ServerInfo> {
+        let response = self.get_with_params(&Path::Home, [("tree", "useSecurity,useCrumbs")])?;
+        let version =
+            header(&response, "X-Jenkins").and_then(|version| Version::from_str(&version).ok());
+        let hudson_version = header(&response, "X-Hudson");
+        let instance_identity = header(&response, "X-Instance-Identity");
+        let security: Security = response.json()?;
+
+        if let Ok(mut cache) = self.server_version.write() {
+            *cache = Some(version.clone());
+        }
+
+        Ok(ServerInfo {
+            version,
+            hudson_version,
+            instance_identity,
+            use_security: security.use_security,
+            use_crumbs: security.use_crumbs,
+        })
+    }
+
+    /// Get the version of Jenkins, only querying the server the first time.
+    /// `None` if the server doesn't announce its version
+    pub fn server_version(&self) -> Result<Option<Version>> {
+        if let Some(version) = self
+            .server_version
+            .read()
+            .ok()
+            .and_then(|cache| cache.clone())
+        {
+            return Ok(version);
+        }
+        Ok(self.server_info()?.version)
+    }
+
+    /// Does the server support a feature. Always false if the server doesn't announce
+    /// its version
+    pub fn supports(&self, feature: Feature) -> Result<bool> {
+        Ok(self
+            .server_version()?
+            .map(|version| version >= feature.minimum_version())
+            .unwrap_or(false))
+    }
+
+    /// Fail early with `UnsupportedVersion` if the server is older than the version
+    /// needed by a feature. Succeeds if the server doesn't announce its version, letting
+    /// the server answer
+    pub fn require(&self, feature: Feature) -> Result<()> {
+        match self.server_version()? {
+            Some(version) if version < feature.minimum_version() => {
+                Err(client::Error::UnsupportedVersion {
+                    feature,
+                    required: feature.minimum_version(),
+                    found: version,
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Name of the master node in URLs, depending on the version
+    pub(crate) fn master_node_name(&self) -> Result<&'static str> {
+        if self.supports(Feature::BuiltInNode)? {
+            Ok("(built-in)")
+        } else {
+            Ok("(master)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_versions() {
+        assert_eq!(
+            Version::from_str("2.263.1").unwrap().components(),
+            &[2, 263, 1]
+        );
+        assert_eq!(
+            Version::from_str("2.277-SNAPSHOT").unwrap().to_string(),
+            "2.277"
+        );
+        assert!(Version::from_str("2.176.2").unwrap() < Version::from_str("2.186").unwrap());
+        assert!(Version::from_str("2.303.1").unwrap() < Feature::BuiltInNode.minimum_version());
+        assert!(Version::from_str("snapshot").is_err());
+    }
+
+    #[test]
+    fn can_get_server_info() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("GET", "/api/json?tree=useSecurity%2CuseCrumbs")
+            .with_header("X-Jenkins", "2.190.1")
+            .with_header("X-Hudson", "1.395")
+            .with_header("X-Instance-Identity", "MIIBIjANBgkqhkiG9w0B")
+            .with_body(r#"{"useSecurity": true, "useCrumbs": false}"#)
+            .create();
+
+        let info = jenkins_client.server_info().unwrap();
+        assert_eq!(info.version, Some(Version(vec![2, 190, 1])));
+        assert_eq!(info.hudson_version, Some("1.395".to_string()));
+        assert!(info.use_security);
+        assert!(!info.use_crumbs);
+
+        assert!(!jenkins_client.supports(Feature::BuiltInNode).unwrap());
+        assert_eq!(jenkins_client.master_node_name().unwrap(), "(master)");
+        match jenkins_client
+            .require(Feature::QuietDownReason)
+            .unwrap_err()
+            .downcast_ref::<client::Error>()
+        {
+            Some(client::Error::UnsupportedVersion {
+                feature,
+                required,
+                found,
+            }) => {
+                assert_eq!(*feature, Feature::QuietDownReason);
+                assert_eq!(required, &Version(vec![2, 267]));
+                assert_eq!(found, &Version(vec![2, 190, 1]));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn caches_missing_version() {
+        let jenkins_client =
+            crate::JenkinsBuilder::new(&format!("{}/hidden", mockito::server_url()))
+                .build()
+                .unwrap();
+
+        let mock = mockito::mock("GET", "/hidden/api/json?tree=useSecurity%2CuseCrumbs")
+            .with_body(r#"{"useSecurity": true, "useCrumbs": false}"#)
+            .expect(1)
+            .create();
+
+        assert_eq!(jenkins_client.server_version().unwrap(), None);
+        assert_eq!(jenkins_client.server_version().unwrap(), None);
+        assert_eq!(jenkins_client.master_node_name().unwrap(), "(master)");
+        assert!(jenkins_client.require(Feature::QuietDownReason).is_ok());
+        mock.assert();
+    }
+}
@@ -0,0 +1,396 @@
+//! Synchronize the jobs of two Jenkins instances
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::client::Result;
+use crate::job::diff::config_diff;
+use crate::job::walker::WalkedJob;
+use crate::xml::{Document, Element};
+use crate::Jenkins;
+
+/// How the jobs of the target are synchronized with the source
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncMode {
+    /// Create, update and delete jobs so that the target matches the source
+    Mirror,
+    /// Create and update jobs, without deleting the jobs missing from the source
+    AdditiveOnly,
+    /// Report what `Mirror` would do, without changing anything
+    DryRun,
+}
+
+/// Operation to synchronize a job
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncOperation {
+    /// The job is missing from the target
+    Create,
+    /// The configuration of the job is different on the target
+    Update,
+    /// The job is missing from the source
+    Delete,
+}
+
+/// An operation done to synchronize a job
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncAction {
+    /// Full name of the job, with the names of the folders containing it separated by `/`
+    pub job: String,
+    /// Operation on the job
+    pub operation: SyncOperation,
+    /// Error if the operation failed, `None` if it succeeded or was not done
+    pub error: Option<String>,
+}
+
+/// A plugin used by jobs of the source, and not installed on the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingPlugin {
+    /// Short name of the plugin
+    pub plugin: String,
+    /// Classes of the plugin used in the configuration of the jobs
+    pub classes: BTreeSet<String>,
+    /// Jobs using the plugin
+    pub jobs: BTreeSet<String>,
+}
+
+/// Report of a synchronization
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncReport {
+    /// Operations done, or planned for a dry run
+    pub actions: Vec<SyncAction>,
+    /// Plugins used by jobs of the source and not installed on the target
+    pub missing_plugins: Vec<MissingPlugin>,
+}
+
+/// Collect the plugins used in a configuration, with the classes they provide. The
+/// class is the `class` attribute of the element declaring the plugin, or its name
+fn collect_plugins(element: &Element, plugins: &mut BTreeMap<String, BTreeSet<String>>) {
+    if let Some(plugin) = element.attribute("plugin") {
+        let name = plugin.split('@').next().unwrap_or_default().to_string();
+        let class = element
+            .attribute("class")
+            .unwrap_or_else(|| element.name.clone());
+        let _ = plugins.entry(name).or_default().insert(class);
+    }
+    for child in element.elements() {
+        collect_plugins(child, plugins);
+    }
+}
+
+/// Create or update a job of the target to match the source, recording the plugins it
+/// uses that are missing on the target. Returns `false` if the job is already up to date
+fn sync_job(
+    source: &Jenkins,
+    target: &Jenkins,
+    full_name: &str,
+    operation: SyncOperation,
+    mode: SyncMode,
+    installed: &BTreeSet<String>,
+    missing_plugins: &mut BTreeMap<String, MissingPlugin>,
+) -> Result<bool> {
+    let config = source.get_job_config_xml(full_name)?;
+
+    let mut plugins = BTreeMap::new();
+    collect_plugins(&Document::parse(&config)?.root, &mut plugins);
+    for (plugin, classes) in plugins {
+        if installed.contains(&plugin) {
+            continue;
+        }
+        let missing = missing_plugins
+            .entry(plugin.clone())
+            .or_insert_with(|| MissingPlugin {
+                plugin,
+                classes: BTreeSet::new(),
+                jobs: BTreeSet::new(),
+            });
+        missing.classes.extend(classes);
+        let _ = missing.jobs.insert(full_name.to_string());
+    }
+
+    if operation == SyncOperation::Update {
+        let live = target.get_job_config_xml(full_name)?;
+        if config_diff(&live, &config)?.is_empty() {
+            return Ok(false);
+        }
+    }
+    match (mode, operation) {
+        (SyncMode::DryRun, _) => {}
+        (_, SyncOperation::Create) => target.create_job_from_xml(full_name, &config)?,
+        (_, _) => target.update_job_config_xml(full_name, &config)?,
+    }
+    Ok(true)
+}
+
+/// Synchronize the jobs of `target` with the jobs of `source` matching `filter`. Only the
+/// jobs matching `filter` are deleted from the target when mirroring. Jobs of multibranch
+/// projects and organization folders are left to be created by scanning their parent.
+/// Once the jobs of both instances are listed, failed operations are reported in the
+/// `SyncReport` without stopping the synchronization
+pub fn sync<F>(source: &Jenkins, target: &Jenkins, filter: F, mode: SyncMode) -> Result<SyncReport>
+where
+    F: Fn(&WalkedJob) -> bool,
+{
+    let mut source_jobs = source
+        .configured_jobs()?
+        .into_iter()
+        .filter(|job| filter(job))
+        .collect::<Vec<_>>();
+    source_jobs.sort_by_key(WalkedJob::depth);
+    let target_jobs = target
+        .configured_jobs()?
+        .into_iter()
+        .filter(|job| filter(job))
+        .map(|job| (job.full_name(), job))
+        .collect::<HashMap<_, _>>();
+
+    let installed = target
+        .get_plugins()?
+        .plugins
+        .into_iter()
+        .map(|plugin| plugin.short_name)
+        .collect::<BTreeSet<_>>();
+    let mut missing_plugins: BTreeMap<String, MissingPlugin> = BTreeMap::new();
+
+    let mut actions = vec![];
+    for job in &source_jobs {
+        let full_name = job.full_name();
+        let operation = if target_jobs.contains_key(&full_name) {
+            SyncOperation::Update
+        } else {
+            SyncOperation::Create
+        };
+        let error = match sync_job(
+            source,
+            target,
+            &full_name,
+            operation,
+            mode,
+            &installed,
+            &mut missing_plugins,
+        ) {
+            Ok(false) => continue,
+            Ok(true) => None,
+            Err(error) => Some(error.to_string()),
+        };
+        actions.push(SyncAction {
+            job: full_name,
+            operation,
+            error,
+        });
+    }
+
+    if mode != SyncMode::AdditiveOnly {
+        let source_names = source_jobs
+            .iter()
+            .map(WalkedJob::full_name)
+            .collect::<BTreeSet<_>>();
+        let mut deleted = target_jobs
+            .keys()
+            .filter(|name| !source_names.contains(*name))
+            .cloned()
+            .collect::<Vec<_>>();
+        deleted.sort();
+        // deleting a folder deletes its content
+        let deleted_folders = deleted.clone();
+        for full_name in deleted {
+            let in_deleted_folder = deleted_folders
+                .iter()
+                .any(|folder| full_name.starts_with(&format!("{}/", folder)));
+            if in_deleted_folder {
+                continue;
+            }
+            let result = match mode {
+                SyncMode::DryRun => Ok(()),
+                _ => target.delete_job(&full_name),
+            };
+            actions.push(SyncAction {
+                job: full_name,
+                operation: SyncOperation::Delete,
+                error: result.err().map(|error| error.to_string()),
+            });
+        }
+    }
+
+    Ok(SyncReport {
+        actions,
+        missing_plugins: missing_plugins.into_values().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_collect_plugins() {
+        let config = Document::parse(
+            r#"<flow-definition plugin="workflow-job@2.40">
+  <definition class="org.jenkinsci.plugins.workflow.cps.CpsScmFlowDefinition" plugin="workflow-cps@2.87">
+    <scm class="hudson.plugins.git.GitSCM" plugin="git@4.5.0"/>
+  </definition>
+  <properties>
+    <com.example.Property plugin="example@1.0"/>
+  </properties>
+</flow-definition>"#,
+        )
+        .unwrap();
+        let mut plugins = BTreeMap::new();
+        collect_plugins(&config.root, &mut plugins);
+
+        assert_eq!(
+            plugins.keys().collect::<Vec<_>>(),
+            vec!["example", "git", "workflow-cps", "workflow-job"]
+        );
+        assert!(plugins["git"].contains("hudson.plugins.git.GitSCM"));
+        assert!(plugins["example"].contains("com.example.Property"));
+        assert!(plugins["workflow-job"].contains("flow-definition"));
+    }
+
+    static FREESTYLE: &str = "<project><description>a job</description></project>";
+
+    fn summary(report: &SyncReport) -> Vec<(&str, SyncOperation, bool)> {
+        report
+            .actions
+            .iter()
+            .map(|action| {
+                (
+                    action.job.as_str(),
+                    action.operation,
+                    action.error.is_some(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn can_sync_instances() {
+        let client = |instance: &str| {
+            crate::JenkinsBuilder::new(&format!("{}/{}", mockito::server_url(), instance))
+                .disable_csrf()
+                .build()
+                .unwrap()
+        };
+        let jobs = |instance: &str, body: &str| {
+            mockito::mock(
+                "GET",
+                mockito::Matcher::Regex(format!(r"^/{}/api/json\?tree=jobs", instance)),
+            )
+            .with_body(body)
+            .create()
+        };
+        let config = |path: &str, body: &str| mockito::mock("GET", path).with_body(body).create();
+        let _mocks = vec![
+            jobs(
+                "source",
+                r#"{"jobs": [
+                    {"name": "same", "url": "/job/same/"},
+                    {"name": "changed", "url": "/job/changed/"},
+                    {"name": "new", "url": "/job/new/"},
+                    {"name": "broken", "url": "/job/broken/"},
+                    {"name": "team", "url": "/job/team/", "jobs": [
+                        {"name": "app", "url": "/job/team/job/app/"}
+                    ]}
+                ]}"#,
+            ),
+            jobs(
+                "target",
+                r#"{"jobs": [
+                    {"name": "same", "url": "/job/same/"},
+                    {"name": "changed", "url": "/job/changed/"},
+                    {"name": "old", "url": "/job/old/"},
+                    {"name": "ignored", "url": "/job/ignored/"},
+                    {"name": "old-folder", "url": "/job/old-folder/", "jobs": [
+                        {"name": "nested", "url": "/job/old-folder/job/nested/"}
+                    ]}
+                ]}"#,
+            ),
+            mockito::mock("GET", "/target/pluginManager/api/json?depth=1")
+                .with_body(
+                    r#"{"plugins": [{"shortName": "workflow-job", "longName": "Pipeline: Job",
+                        "version": "2.40", "enabled": true, "active": true, "hasUpdate": false}]}"#,
+                )
+                .create(),
+            config("/source/job/same/config.xml", FREESTYLE),
+            config("/target/job/same/config.xml", FREESTYLE),
+            config(
+                "/source/job/changed/config.xml",
+                "<project><description>changed</description></project>",
+            ),
+            config("/target/job/changed/config.xml", FREESTYLE),
+            config(
+                "/source/job/new/config.xml",
+                r#"<project><builders><com.example.Builder plugin="example@1.0"/></builders></project>"#,
+            ),
+            mockito::mock("GET", "/source/job/broken/config.xml")
+                .with_status(500)
+                .create(),
+            config(
+                "/source/job/team/config.xml",
+                "<com.cloudbees.hudson.plugins.folder.Folder/>",
+            ),
+            config(
+                "/source/job/team/job/app/config.xml",
+                r#"<flow-definition plugin="workflow-job@2.40"/>"#,
+            ),
+        ];
+        let (source, target) = (client("source"), client("target"));
+        let not_ignored = |job: &WalkedJob| job.job.name != "ignored";
+
+        let create = |path: &str| {
+            mockito::mock("POST", path)
+                .match_header("content-type", "application/xml")
+                .expect(2)
+                .create()
+        };
+        let created = [
+            create("/target/createItem?name=new"),
+            create("/target/createItem?name=team"),
+            create("/target/job/team/createItem?name=app"),
+        ];
+        let updated = mockito::mock("POST", "/target/job/changed/config.xml")
+            .match_body("<project><description>changed</description></project>")
+            .expect(2)
+            .create();
+        let deleted = vec![
+            mockito::mock("POST", "/target/job/old/doDelete").create(),
+            mockito::mock("POST", "/target/job/old-folder/doDelete").create(),
+        ];
+
+        let changes = vec![
+            ("changed", SyncOperation::Update, false),
+            ("new", SyncOperation::Create, false),
+            ("broken", SyncOperation::Create, true),
+            ("team", SyncOperation::Create, false),
+            ("team/app", SyncOperation::Create, false),
+        ];
+        let mut mirrored = changes.clone();
+        mirrored.push(("old", SyncOperation::Delete, false));
+        mirrored.push(("old-folder", SyncOperation::Delete, false));
+
+        let report = sync(&source, &target, not_ignored, SyncMode::DryRun).unwrap();
+        assert_eq!(summary(&report), mirrored);
+        assert_eq!(
+            report.missing_plugins,
+            vec![MissingPlugin {
+                plugin: "example".to_string(),
+                classes: vec!["com.example.Builder".to_string()]
+                    .into_iter()
+                    .collect(),
+                jobs: vec!["new".to_string()].into_iter().collect(),
+            }]
+        );
+
+        let report = sync(&source, &target, not_ignored, SyncMode::AdditiveOnly).unwrap();
+        assert_eq!(summary(&report), changes);
+
+        let report = sync(&source, &target, not_ignored, SyncMode::Mirror).unwrap();
+        assert_eq!(summary(&report), mirrored);
+
+        // dry runs don't change the target
+        for mock in created.iter().chain(std::iter::once(&updated)) {
+            mock.assert();
+        }
+        for mock in &deleted {
+            mock.assert();
+        }
+    }
+}
@@ -2,6 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::client::Result;
+use crate::client_internals::{Name, Path};
+use crate::job::ShortJob;
+use crate::property::{CommonProperty, MailerUserProperty};
+use crate::Jenkins;
+
 /// Short User that is used in list and links from other structs
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -19,3 +25,134 @@ pub struct ShortUser {
     #[serde(flatten)]
     pub extra_fields: Option<serde_json::Value>,
 }
+
+impl ShortUser {
+    /// Get the full details of a `User` matching the `ShortUser`
+    pub fn get_full_user(&self, jenkins_client: &Jenkins) -> Result<User> {
+        let id = self
+            .absolute_url
+            .trim_end_matches('/')
+            .rsplit("/user/")
+            .next()
+            .unwrap_or_default();
+        Ok(jenkins_client
+            .get(&Path::User {
+                id: Name::UrlEncodedName(id),
+            })?
+            .json()?)
+    }
+}
+
+/// A Jenkins `User`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    /// ID of the user
+    pub id: String,
+    /// Full name of the user
+    pub full_name: String,
+    /// Absolute URL to the user profile
+    pub absolute_url: String,
+    /// Description of the user
+    pub description: Option<String>,
+    /// Properties of the user
+    #[serde(default)]
+    pub property: Vec<CommonProperty>,
+}
+
+impl User {
+    /// Email address of the user, from the `Mailer` plugin
+    pub fn email(&self) -> Option<String> {
+        self.property
+            .iter()
+            .find_map(|property| property.as_variant::<MailerUserProperty>().ok())
+            .and_then(|mailer| mailer.address)
+    }
+}
+
+/// The user making the requests
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WhoAmI {
+    /// Name of the user
+    pub name: String,
+    /// Is the user anonymous
+    pub anonymous: bool,
+    /// Is the user authenticated
+    pub authenticated: bool,
+    /// Authorities granted to the user, like groups
+    #[serde(default)]
+    pub authorities: Vec<String>,
+}
+
+/// A user known to Jenkins, with their last activity
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    /// The user
+    pub user: ShortUser,
+    /// Timestamp of the last change made by the user
+    pub last_change: Option<u64>,
+    /// Project of the last change made by the user
+    pub project: Option<ShortJob>,
+}
+
+/// List of users known to Jenkins
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct People {
+    /// Users
+    pub users: Vec<Person>,
+}
+
+impl Jenkins {
+    /// Get the user making the requests, with their authorities
+    pub fn who_am_i(&self) -> Result<WhoAmI> {
+        Ok(self.get(&Path::WhoAmI)?.json()?)
+    }
+
+    /// Get a `User` by its ID
+    pub fn get_user(&self, id: &str) -> Result<User> {
+        Ok(self.get(&Path::User { id: Name::Name(id) })?.json()?)
+    }
+
+    /// List the users known to Jenkins, including the authors of changes
+    pub fn list_people(&self) -> Result<People> {
+        Ok(self.get(&Path::People)?.json()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn can_get_full_user_with_email() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .build()
+            .unwrap();
+
+        let _mock = mockito::mock("GET", "/user/jane%20doe/api/json?depth=1")
+            .with_body(
+                r#"{
+                    "_class": "hudson.model.User",
+                    "absoluteUrl": "http://localhost:8080/user/jane%20doe",
+                    "description": null,
+                    "fullName": "Jane Doe",
+                    "id": "jane doe",
+                    "property": [
+                        {"_class": "jenkins.security.ApiTokenProperty"},
+                        {"_class": "hudson.tasks.Mailer$UserProperty", "address": "jane@example.com"}
+                    ]
+                }"#,
+            )
+            .create();
+
+        let short_user: super::ShortUser = serde_json::from_str(&format!(
+            r#"{{"fullName": "Jane Doe", "absoluteUrl": "{}/user/jane%20doe"}}"#,
+            mockito::server_url()
+        ))
+        .unwrap();
+        let user = short_user.get_full_user(&jenkins_client).unwrap();
+        assert_eq!(user.id, "jane doe");
+        assert_eq!(user.email(), Some("jane@example.com".to_string()));
+    }
+}
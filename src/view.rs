@@ -8,8 +8,45 @@ use crate::client::{self, Result};
 use crate::client_internals::{Name, Path};
 use crate::job::{JobName, ShortJob};
 use crate::property::CommonProperty;
+use crate::xml;
 use crate::Jenkins;
 
+/// Columns displayed by default in a `ListView`
+pub const DEFAULT_LIST_VIEW_COLUMNS: [&str; 7] = [
+    "hudson.views.StatusColumn",
+    "hudson.views.WeatherColumn",
+    "hudson.views.JobColumn",
+    "hudson.views.LastSuccessColumn",
+    "hudson.views.LastFailureColumn",
+    "hudson.views.LastDurationColumn",
+    "hudson.views.BuildButtonColumn",
+];
+
+/// Type of view that can be created
+#[derive(Debug, Clone, Copy)]
+pub enum ViewType<'a> {
+    /// A `ListView`
+    ListView,
+    /// A view of the jobs the current user can access
+    MyView,
+    /// A view containing other views, from the nested view plugin
+    NestedView,
+    /// A view from its _class
+    Other(&'a str),
+}
+
+impl<'a> ViewType<'a> {
+    /// _class of this type of view
+    pub fn class(&self) -> &'a str {
+        match self {
+            ViewType::ListView => "hudson.model.ListView",
+            ViewType::MyView => "hudson.model.MyView",
+            ViewType::NestedView => "hudson.plugins.nested_view.NestedView",
+            ViewType::Other(class) => class,
+        }
+    }
+}
+
 /// Short View that is used in lists and links from other structs
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -31,8 +68,10 @@ pub struct ShortView {
 impl ShortView {
     /// Get the full details of a `View` matching the `ShortView`
     pub fn get_full_view(&self, jenkins_client: &Jenkins) -> Result<CommonView> {
-        let path = jenkins_client.url_to_path(&self.url);
-        if let Path::View { .. } = path {
+        let path = jenkins_client
+            .url_to_path(&self.url)
+            .map_view(|name| Path::View { name });
+        if let Some(path) = path {
             Ok(jenkins_client.get(&path)?.json()?)
         } else {
             Err(client::Error::InvalidUrl {
@@ -74,60 +113,177 @@ pub trait View {
     fn name(&self) -> &str;
 }
 
-/// A Jenkins `View` with a list of `ShortJob`
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct CommonView {
-    /// _class provided by Jenkins
-    #[serde(rename = "_class")]
-    pub class: Option<String>,
-    /// Description of the view
-    pub description: Option<String>,
-    /// Name of the view
-    pub name: String,
-    /// URL for the view
-    pub url: String,
-    /// List of jobs in the view
-    pub jobs: Vec<ShortJob>,
-    /// Properties of the view
-    pub property: Vec<CommonProperty>,
+/// Get the path of an action on the view at `url`
+fn view_action_path<'a, F>(jenkins_client: &Jenkins, url: &'a str, action: F) -> Result<Path<'a>>
+where
+    F: FnOnce(Name<'a>) -> Path<'a>,
+{
+    jenkins_client
+        .url_to_path(url)
+        .map_view(action)
+        .ok_or_else(|| {
+            client::Error::InvalidUrl {
+                url: url.to_string(),
+                expected: client::error::ExpectedType::View,
+            }
+            .into()
+        })
+}
 
-    #[cfg(not(feature = "extra-fields-visibility"))]
-    #[serde(flatten)]
-    extra_fields: serde_json::Value,
-    #[cfg(feature = "extra-fields-visibility")]
-    /// Extra fields not parsed for a common object
-    #[serde(flatten)]
-    pub extra_fields: serde_json::Value,
+macro_rules! view_with_common_fields_and_impl {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                pub $field:ident: $field_type:ty,
+            )*
+            $(private_fields {
+                $(
+                    $(#[$private_field_attr:meta])*
+                    $private_field:ident: $private_field_type:ty
+                ),* $(,)*
+            })*
+        }
+    ) => {
+        $(#[$attr])*
+        pub struct $name {
+            /// Description of the view
+            pub description: Option<String>,
+            /// Name of the view
+            pub name: String,
+            /// URL for the view
+            pub url: String,
+            /// List of jobs in the view
+            #[serde(default)]
+            pub jobs: Vec<ShortJob>,
+            /// Properties of the view
+            #[serde(default)]
+            pub property: Vec<CommonProperty>,
+            $(
+                $(#[$field_attr])*
+                pub $field: $field_type,
+            )*
+            $($(
+                $(#[$private_field_attr])*
+                $private_field: $private_field_type,
+            )*)*
+        }
+        impl View for $name {
+            fn name(&self) -> &str {
+                &self.name
+            }
+        }
+        impl $name {
+            /// Get the config.xml file for this view
+            pub fn get_config_xml(&self, jenkins_client: &Jenkins) -> Result<String> {
+                let path = view_action_path(jenkins_client, &self.url, |name| {
+                    Path::ViewConfigXML { name }
+                })?;
+                Ok(jenkins_client.get_raw(&path)?.text()?)
+            }
+
+            /// Update the config.xml file for this view. It may need to be refreshed as it
+            /// may have been updated
+            pub fn update_config_xml(&self, jenkins_client: &Jenkins, config: &str) -> Result<()> {
+                let path = view_action_path(jenkins_client, &self.url, |name| {
+                    Path::ViewConfigXML { name }
+                })?;
+                let _ = jenkins_client.post_xml(&path, config.to_string(), &[])?;
+                Ok(())
+            }
+
+            /// Delete this view
+            pub fn delete(&self, jenkins_client: &Jenkins) -> Result<()> {
+                let path =
+                    view_action_path(jenkins_client, &self.url, |name| Path::DeleteView { name })?;
+                let _ = jenkins_client.post(&path)?;
+                Ok(())
+            }
+        }
+    };
 }
-specialize!(CommonView => View);
-impl View for CommonView {
-    fn name(&self) -> &str {
-        &self.name
+
+view_with_common_fields_and_impl!(
+    /// A Jenkins `View` with a list of `ShortJob`
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CommonView {
+        /// _class provided by Jenkins
+        #[serde(rename = "_class")]
+        pub class: Option<String>,
+
+        #[cfg(feature = "extra-fields-visibility")]
+        /// Extra fields not parsed for a common object
+        #[serde(flatten)]
+        pub extra_fields: serde_json::Value,
+        private_fields {
+            #[cfg(not(feature = "extra-fields-visibility"))]
+            #[serde(flatten)]
+            extra_fields: serde_json::Value,
+        }
     }
-}
+);
+specialize!(CommonView => View);
 
-/// A Jenkins `View` with a list of `ShortJob`
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct ListView {
-    /// Description of the view
-    pub description: Option<String>,
-    /// Name of the view
-    pub name: String,
-    /// URL for the view
-    pub url: String,
-    /// List of jobs in the view
-    pub jobs: Vec<ShortJob>,
-    /// Properties of the view
-    pub property: Vec<CommonProperty>,
-}
+view_with_common_fields_and_impl!(
+    /// A Jenkins `View` with a list of `ShortJob`
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ListView {}
+);
 register_class!("hudson.model.ListView" => ListView);
-impl View for ListView {
-    fn name(&self) -> &str {
-        &self.name
+
+view_with_common_fields_and_impl!(
+    /// The view with all the jobs
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AllView {}
+);
+register_class!("hudson.model.AllView" => AllView);
+
+view_with_common_fields_and_impl!(
+    /// A view of the jobs the current user can access
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MyView {}
+);
+register_class!("hudson.model.MyView" => MyView);
+
+view_with_common_fields_and_impl!(
+    /// A view showing a global view in the views of a user
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ProxyView {}
+);
+register_class!("hudson.model.ProxyView" => ProxyView);
+
+view_with_common_fields_and_impl!(
+    /// A view containing other views, from the nested view plugin
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct NestedView {
+        /// List of views in this view
+        pub views: Vec<ShortView>,
     }
-}
+);
+register_class!("hudson.plugins.nested_view.NestedView" => NestedView);
+
+view_with_common_fields_and_impl!(
+    /// A view with portlets, from the dashboard view plugin
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DashboardView {}
+);
+register_class!("hudson.plugins.view.dashboard.Dashboard" => DashboardView);
+
+view_with_common_fields_and_impl!(
+    /// A view of upstream and downstream jobs, from the build pipeline plugin
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BuildPipelineView {}
+);
+register_class!("au.com.centrumsystems.hudson.plugin.buildpipeline.BuildPipelineView" => BuildPipelineView);
 
 impl ListView {
     /// Add the job `job_name` to this view
@@ -135,12 +291,15 @@ impl ListView {
     where
         J: Into<JobName<'a>>,
     {
-        let path = jenkins_client.url_to_path(&self.url);
-        if let Path::View { name } = path {
-            let _ = jenkins_client.post(&Path::AddJobToView {
-                job_name: Name::Name(&job_name.into().0),
+        let job_name = job_name.into().0;
+        let path = jenkins_client
+            .url_to_path(&self.url)
+            .map_view(|name| Path::AddJobToView {
+                job_name: Name::Name(job_name),
                 view_name: name,
-            })?;
+            });
+        if let Some(path) = path {
+            let _ = jenkins_client.post(&path)?;
             Ok(())
         } else {
             Err(client::Error::InvalidUrl {
@@ -156,12 +315,15 @@ impl ListView {
     where
         J: Into<JobName<'a>>,
     {
-        let path = jenkins_client.url_to_path(&self.url);
-        if let Path::View { name } = path {
-            let _ = jenkins_client.post(&Path::RemoveJobFromView {
-                job_name: Name::Name(&job_name.into().0),
+        let job_name = job_name.into().0;
+        let path = jenkins_client
+            .url_to_path(&self.url)
+            .map_view(|name| Path::RemoveJobFromView {
+                job_name: Name::Name(job_name),
                 view_name: name,
-            })?;
+            });
+        if let Some(path) = path {
+            let _ = jenkins_client.post(&path)?;
             Ok(())
         } else {
             Err(client::Error::InvalidUrl {
@@ -171,18 +333,78 @@ impl ListView {
             .into())
         }
     }
+
+    /// Set the regular expression used to include jobs in this view, or remove it
+    /// with `None`. It may need to be refreshed as it may have been updated
+    pub fn set_include_regex(&self, jenkins_client: &Jenkins, regex: Option<&str>) -> Result<()> {
+        let mut config = xml::Document::parse(&self.get_config_xml(jenkins_client)?)?;
+        match regex {
+            Some(regex) => config.root.set_child_text("includeRegex", regex),
+            None => config.root.remove_child("includeRegex"),
+        }
+        self.update_config_xml(jenkins_client, &config.to_string())
+    }
+
+    /// Set the columns displayed in this view, from their _class. See
+    /// [`DEFAULT_LIST_VIEW_COLUMNS`](constant.DEFAULT_LIST_VIEW_COLUMNS.html) for the
+    /// columns of a new view. It may need to be refreshed as it may have been updated
+    pub fn set_columns(&self, jenkins_client: &Jenkins, columns: &[&str]) -> Result<()> {
+        let mut config = xml::Document::parse(&self.get_config_xml(jenkins_client)?)?;
+        let mut columns_element = xml::Element::new("columns");
+        for column in columns {
+            columns_element.push_child(xml::Element::new(column));
+        }
+        config.root.replace_child(columns_element);
+        self.update_config_xml(jenkins_client, &config.to_string())
+    }
 }
 
 impl Jenkins {
-    /// Get a `View`
+    /// Create a view named `view_name` of type `view_type`. To create it in a `NestedView`,
+    /// prefix its name with the name of the parent view, like `parent/child`
+    pub fn create_view<'a, V>(&self, view_name: V, view_type: ViewType) -> Result<()>
+    where
+        V: Into<ViewName<'a>>,
+    {
+        let (path, view_name) = create_view_path(view_name.into().0);
+        let json = serde_json::json!({
+            "name": view_name,
+            "mode": view_type.class(),
+        })
+        .to_string();
+        let _ = self.post_with_body(
+            &path,
+            serde_urlencoded::to_string([
+                ("name", view_name),
+                ("mode", view_type.class()),
+                ("json", &json),
+            ])?,
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Create a view named `view_name` from its config.xml file. To create it in a
+    /// `NestedView`, prefix its name with the name of the parent view, like `parent/child`
+    pub fn create_view_from_xml<'a, V>(&self, view_name: V, config: &str) -> Result<()>
+    where
+        V: Into<ViewName<'a>>,
+    {
+        let (path, view_name) = create_view_path(view_name.into().0);
+        let _ = self.post_xml(&path, config.to_string(), &[("name", view_name)])?;
+        Ok(())
+    }
+
+    /// Get a `View`. Views nested in other views are named with the names of their
+    /// parents separated by `/`, like `parent/child`
     pub fn get_view<'a, V>(&self, view_name: V) -> Result<CommonView>
     where
         V: Into<ViewName<'a>>,
     {
         Ok(self
-            .get(&Path::View {
-                name: Name::Name(&view_name.into().0),
-            })?
+            .get(&Path::for_view(view_name.into().0, |name| Path::View {
+                name,
+            }))?
             .json()?)
     }
 
@@ -192,10 +414,13 @@ impl Jenkins {
         V: Into<ViewName<'a>>,
         J: Into<JobName<'a>>,
     {
-        let _ = self.post(&Path::AddJobToView {
-            job_name: Name::Name(&job_name.into().0),
-            view_name: Name::Name(&view_name.into().0),
-        })?;
+        let job_name = job_name.into().0;
+        let _ = self.post(&Path::for_view(view_name.into().0, |name| {
+            Path::AddJobToView {
+                job_name: Name::Name(job_name),
+                view_name: name,
+            }
+        }))?;
         Ok(())
     }
 
@@ -205,10 +430,56 @@ impl Jenkins {
         V: Into<ViewName<'a>>,
         J: Into<JobName<'a>>,
     {
-        let _ = self.post(&Path::AddJobToView {
-            job_name: Name::Name(&job_name.into().0),
-            view_name: Name::Name(&view_name.into().0),
-        })?;
+        let job_name = job_name.into().0;
+        let _ = self.post(&Path::for_view(view_name.into().0, |name| {
+            Path::RemoveJobFromView {
+                job_name: Name::Name(job_name),
+                view_name: name,
+            }
+        }))?;
         Ok(())
     }
 }
+
+fn create_view_path(view_name: &str) -> (Path<'_>, &str) {
+    match view_name.rfind('/') {
+        Some(index) => (
+            Path::for_view(&view_name[..index], |name| Path::InView {
+                view_name: name,
+                path: Box::new(Path::CreateView),
+            }),
+            &view_name[(index + 1)..],
+        ),
+        None => (Path::CreateView, view_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_remove_job_from_view() {
+        let jenkins_client = crate::JenkinsBuilder::new(&mockito::server_url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let removed = mockito::mock("POST", "/view/remove/removeJobFromView?name=job")
+            .expect(2)
+            .create();
+        let added = mockito::mock("POST", "/view/remove/addJobToView?name=job")
+            .expect(0)
+            .create();
+
+        assert!(jenkins_client.remove_job_from_view("remove", "job").is_ok());
+        let view: ListView = serde_json::from_str(&format!(
+            r#"{{"name": "remove", "url": "{}/view/remove/"}}"#,
+            mockito::server_url()
+        ))
+        .unwrap();
+        assert!(view.remove_job(&jenkins_client, "job").is_ok());
+        removed.assert();
+        added.assert();
+    }
+}
@@ -0,0 +1,368 @@
+//! Minimal XML tree used to edit configuration files without losing unknown content
+
+use std::fmt;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::client::Result;
+
+/// A node in an XML element
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    /// A child element
+    Element(Element),
+    /// Text, kept escaped as in the source document
+    Text(String),
+    /// A CDATA section
+    CData(String),
+    /// A comment
+    Comment(String),
+    /// A processing instruction
+    ProcessingInstruction(String),
+}
+
+/// An XML element, with its attributes and children
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    /// Attributes, with their values kept escaped as in the source document
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Node>,
+}
+
+/// An XML document
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Document {
+    declaration: Option<String>,
+    prolog: Vec<Node>,
+    pub(crate) root: Element,
+}
+
+impl Element {
+    pub(crate) fn new(name: &str) -> Self {
+        Element {
+            name: name.to_string(),
+            attributes: vec![],
+            children: vec![],
+        }
+    }
+
+    /// Create an element containing only `text`
+    pub(crate) fn with_text(name: &str, text: &str) -> Self {
+        let mut element = Element::new(name);
+        element.set_text(text);
+        element
+    }
+
+    /// Iterate over child elements
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    /// Get the first child element named `name`
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    /// Get the first element named `name` among the descendants, depth first
+    #[cfg(test)]
+    pub(crate) fn descendant(&self, name: &str) -> Option<&Element> {
+        self.elements().find_map(|element| {
+            if element.name == name {
+                Some(element)
+            } else {
+                element.descendant(name)
+            }
+        })
+    }
+
+    /// Get the first child element named `name`
+    pub(crate) fn child_mut(&mut self, name: &str) -> Option<&mut Element> {
+        self.children.iter_mut().find_map(|node| match node {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    /// Unescaped value of the attribute `name`
+    pub(crate) fn attribute(&self, name: &str) -> Option<String> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| unescape(value))
+    }
+
+    /// Set the value of the attribute `name`, adding it if it's missing
+    pub(crate) fn set_attribute(&mut self, name: &str, value: &str) {
+        let value = escape(value);
+        match self.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+    }
+
+    /// Unescaped text content of the element
+    pub(crate) fn text(&self) -> String {
+        self.children
+            .iter()
+            .map(|node| match node {
+                Node::Text(text) => unescape(text),
+                Node::CData(text) => text.clone(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    /// Replace the content of the element with `text`
+    pub(crate) fn set_text(&mut self, text: &str) {
+        self.children = if text.is_empty() {
+            vec![]
+        } else {
+            vec![Node::Text(escape(text))]
+        };
+    }
+
+    /// Set the text of the first child element named `name`, adding it at the
+    /// end of the children if it's missing
+    pub(crate) fn set_child_text(&mut self, name: &str, text: &str) {
+        match self.child_mut(name) {
+            Some(element) => element.set_text(text),
+            None => self.push_child(Element::with_text(name, text)),
+        }
+    }
+
+    /// Replace the first child element with the same name as `child`, adding it at
+    /// the end of the children if it's missing
+    pub(crate) fn replace_child(&mut self, child: Element) {
+        match self.child_mut(&child.name) {
+            Some(element) => *element = child,
+            None => self.push_child(child),
+        }
+    }
+
+    /// Add a child element at the end of the children, keeping the indentation
+    /// used by the other children
+    pub(crate) fn push_child(&mut self, child: Element) {
+        let closing = match self.children.last() {
+            Some(Node::Text(text)) if text.trim().is_empty() => self.children.pop(),
+            _ => None,
+        };
+        if let Some(Node::Text(closing)) = closing {
+            let indentation = self
+                .children
+                .iter()
+                .rev()
+                .find_map(|node| match node {
+                    Node::Text(text) if text.trim().is_empty() => Some(text.clone()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| format!("{}  ", closing));
+            self.children.push(Node::Text(indentation));
+            self.children.push(Node::Element(child));
+            self.children.push(Node::Text(closing));
+        } else {
+            self.children.push(Node::Element(child));
+        }
+    }
+
+    /// Remove all child elements named `name`, with the whitespace preceding them
+    pub(crate) fn remove_child(&mut self, name: &str) {
+        let mut children = Vec::with_capacity(self.children.len());
+        for node in self.children.drain(..) {
+            match node {
+                Node::Element(ref element) if element.name == name => {
+                    if let Some(Node::Text(text)) = children.last() {
+                        if text.trim().is_empty() {
+                            let _ = children.pop();
+                        }
+                    }
+                }
+                node => children.push(node),
+            }
+        }
+        self.children = children;
+    }
+
+    fn write(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}", self.name)?;
+        for (key, value) in &self.attributes {
+            write!(f, " {}=\"{}\"", key, value.replace('"', "&quot;"))?;
+        }
+        if self.children.is_empty() {
+            return write!(f, "/>");
+        }
+        write!(f, ">")?;
+        for child in &self.children {
+            child.write(f)?;
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+impl Node {
+    fn write(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Node::Element(element) => element.write(f),
+            Node::Text(text) => write!(f, "{}", text),
+            Node::CData(text) => write!(f, "<![CDATA[{}]]>", text),
+            Node::Comment(text) => write!(f, "<!--{}-->", text),
+            Node::ProcessingInstruction(text) => write!(f, "<?{}?>", text),
+        }
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f)
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref declaration) = self.declaration {
+            write!(f, "<?{}?>", declaration)?;
+        }
+        for node in &self.prolog {
+            node.write(f)?;
+        }
+        self.root.write(f)
+    }
+}
+
+impl Document {
+    /// Parse an XML document
+    pub(crate) fn parse(xml: &str) -> Result<Document> {
+        let mut reader = Reader::from_str(xml);
+        let _ = reader.trim_text(false).check_end_names(true);
+        let mut buf = Vec::new();
+
+        let mut declaration = None;
+        let mut prolog = vec![];
+        let mut stack: Vec<Element> = vec![];
+        let mut root = None;
+
+        loop {
+            let node = match reader.read_event(&mut buf)? {
+                Event::Decl(decl) => {
+                    declaration = Some(to_string(&decl)?);
+                    None
+                }
+                Event::Start(start) => {
+                    stack.push(start_element(&start)?);
+                    None
+                }
+                Event::Empty(start) => Some(Node::Element(start_element(&start)?)),
+                Event::End(_) => stack.pop().map(Node::Element),
+                Event::Text(text) => Some(Node::Text(to_string(text.escaped())?)),
+                Event::CData(text) => Some(Node::CData(to_string(&text)?)),
+                Event::Comment(text) => Some(Node::Comment(to_string(text.escaped())?)),
+                Event::PI(text) => Some(Node::ProcessingInstruction(to_string(text.escaped())?)),
+                Event::DocType(_) => None,
+                Event::Eof => break,
+            };
+            match (node, stack.last_mut()) {
+                (Some(node), Some(parent)) => parent.children.push(node),
+                (Some(Node::Element(element)), None) => root = Some(element),
+                (Some(node), None) if root.is_none() => prolog.push(node),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(Document {
+            declaration,
+            prolog,
+            root: root
+                .ok_or_else(|| quick_xml::Error::UnexpectedEof("root element".to_string()))?,
+        })
+    }
+}
+
+fn start_element(start: &quick_xml::events::BytesStart) -> Result<Element> {
+    let mut element = Element::new(&to_string(start.name())?);
+    for attribute in start.attributes() {
+        let attribute = attribute?;
+        element
+            .attributes
+            .push((to_string(attribute.key)?, to_string(&attribute.value)?));
+    }
+    Ok(element)
+}
+
+fn to_string(bytes: &[u8]) -> Result<String> {
+    Ok(std::str::from_utf8(bytes)?.to_string())
+}
+
+/// Escape `text` to be used as XML text or attribute value
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Unescape XML text or attribute value
+pub(crate) fn unescape(text: &str) -> String {
+    match quick_xml::escape::unescape(text.as_bytes()) {
+        Ok(unescaped) => String::from_utf8_lossy(&unescaped).to_string(),
+        Err(_) => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static CONFIG: &str = "<?xml version='1.1' encoding='UTF-8'?>
+<hudson.model.ListView>
+  <name>my view</name>
+  <filterExecutors>false</filterExecutors>
+  <properties class=\"hudson.model.View$PropertyList\"/>
+  <jobNames>
+    <comparator class=\"hudson.util.CaseInsensitiveComparator\"/>
+  </jobNames>
+  <!-- a comment -->
+  <description>a &lt;b&gt; description</description>
+</hudson.model.ListView>";
+
+    #[test]
+    fn can_round_trip() {
+        let document = Document::parse(CONFIG).unwrap();
+        assert_eq!(document.to_string(), CONFIG);
+    }
+
+    #[test]
+    fn can_read_text() {
+        let document = Document::parse(CONFIG).unwrap();
+        assert_eq!(
+            document.root.child("description").map(Element::text),
+            Some("a <b> description".to_string())
+        );
+        assert_eq!(document.root.elements().count(), 5);
+    }
+
+    #[test]
+    fn can_edit_children() {
+        let mut document = Document::parse(CONFIG).unwrap();
+        document.root.set_child_text("filterExecutors", "true");
+        document.root.set_child_text("includeRegex", "team-.*");
+        document.root.remove_child("jobNames");
+        assert_eq!(
+            document.to_string(),
+            "<?xml version='1.1' encoding='UTF-8'?>
+<hudson.model.ListView>
+  <name>my view</name>
+  <filterExecutors>true</filterExecutors>
+  <properties class=\"hudson.model.View$PropertyList\"/>
+  <!-- a comment -->
+  <description>a &lt;b&gt; description</description>
+  <includeRegex>team-.*</includeRegex>
+</hudson.model.ListView>"
+        );
+    }
+}
@@ -741,3 +741,651 @@ fn can_get_by_path_with_depth() {
 
     assert!(r.is_ok());
 }
+
+#[test]
+fn can_get_by_path_as_xml() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    #[derive(Deserialize, Debug)]
+    struct Names {
+        #[serde(rename = "name", default)]
+        names: Vec<String>,
+    }
+
+    let xml = jenkins.get_object_xml(
+        jenkins_api::client::Path::Home,
+        Some("//job/name"),
+        Some("names"),
+    );
+    assert_that!(xml).is_ok();
+    assert_that!(xml.unwrap()).contains("<names>");
+
+    let names: Result<Names, _> = jenkins.get_object_xml_as(
+        jenkins_api::client::Path::Home,
+        Some("//job/name"),
+        Some("names"),
+    );
+    assert_that!(names).is_ok();
+    assert_that!(names.unwrap().names).contains("normal job".to_string());
+}
+
+#[test]
+fn can_walk_jobs() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let jobs = jenkins.walk_jobs();
+    assert_that!(jobs).is_ok();
+    let names: Vec<String> = jobs.unwrap().map(|job| job.full_name()).collect();
+    assert_that!(names).contains("normal job".to_string());
+
+    let pipelines = jenkins
+        .job_walker()
+        .with_class::<jenkins_api::job::WorkflowJob>()
+        .walk();
+    assert_that!(pipelines).is_ok();
+    for job in pipelines.unwrap() {
+        assert_that!(job.class).is_equal_to(Some(
+            "org.jenkinsci.plugins.workflow.job.WorkflowJob".to_string(),
+        ));
+    }
+}
+
+#[test]
+fn can_create_configure_and_delete_view() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let created = jenkins.create_view("created view", jenkins_api::view::ViewType::ListView);
+    assert_that!(created).is_ok();
+
+    let view = jenkins
+        .get_view("created view")
+        .unwrap()
+        .as_variant::<jenkins_api::view::ListView>()
+        .unwrap();
+
+    assert_that!(view.set_include_regex(&jenkins, Some("normal.*"))).is_ok();
+    assert_that!(view.set_columns(
+        &jenkins,
+        &jenkins_api::view::DEFAULT_LIST_VIEW_COLUMNS[0..3]
+    ))
+    .is_ok();
+    let config = view.get_config_xml(&jenkins);
+    assert_that!(config).is_ok();
+    let config = config.unwrap();
+    assert_that!(config).contains("<includeRegex>normal.*</includeRegex>");
+    assert_that!(config.contains("hudson.views.BuildButtonColumn")).is_false();
+
+    let refreshed = jenkins.get_view("created view").unwrap();
+    assert_that!(refreshed.jobs.iter().any(|job| job.name == "normal job")).is_true();
+
+    assert_that!(view.delete(&jenkins)).is_ok();
+    assert_that!(jenkins.get_view("created view")).is_err();
+}
+
+#[test]
+fn can_create_and_get_nested_view() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    assert_that!(jenkins.create_view("parent view", jenkins_api::view::ViewType::NestedView))
+        .is_ok();
+    assert_that!(jenkins.create_view(
+        "parent view/child view",
+        jenkins_api::view::ViewType::ListView
+    ))
+    .is_ok();
+
+    let parent = jenkins
+        .get_view("parent view")
+        .unwrap()
+        .as_variant::<jenkins_api::view::NestedView>();
+    assert_that!(parent).is_ok();
+    let parent = parent.unwrap();
+    assert_that!(parent.views.len()).is_equal_to(1);
+
+    let child = parent.views[0].get_full_view(&jenkins);
+    assert_that!(child).is_ok();
+    assert_that!(child.unwrap().name).is_equal_to("child view".to_string());
+
+    let child = jenkins.get_view("parent view/child view");
+    assert_that!(child).is_ok();
+    let child = child
+        .unwrap()
+        .as_variant::<jenkins_api::view::ListView>()
+        .unwrap();
+    assert_that!(child.add_job(&jenkins, "normal job")).is_ok();
+
+    assert_that!(parent.delete(&jenkins)).is_ok();
+}
+
+#[test]
+fn can_manage_agent_lifecycle() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let created = jenkins
+        .permanent_agent_builder("created agent")
+        .with_labels("linux docker")
+        .with_executors(2)
+        .send();
+    assert_that!(created).is_ok();
+
+    let node = jenkins.get_node("created agent");
+    assert_that!(node).is_ok();
+    assert_that!(node.unwrap().num_executors).is_equal_to(2);
+
+    assert_that!(jenkins.toggle_node_offline("created agent", Some("maintenance"))).is_ok();
+    let node = jenkins.get_node("created agent").unwrap();
+    assert_that!(node.temporarily_offline).is_true();
+
+    let config = jenkins.get_node_config_xml("created agent");
+    assert_that!(config).is_ok();
+    let config = config.unwrap().replace(
+        "<numExecutors>2</numExecutors>",
+        "<numExecutors>3</numExecutors>",
+    );
+    assert_that!(jenkins.update_node_config_xml("created agent", &config)).is_ok();
+    assert_that!(jenkins.get_node("created agent").unwrap().num_executors).is_equal_to(3);
+
+    assert_that!(jenkins.get_node_secret("created agent")).is_ok();
+
+    assert_that!(jenkins.delete_node("created agent")).is_ok();
+    assert_that!(jenkins.get_node("created agent")).is_err();
+}
+
+#[test]
+fn can_get_label_and_matching_nodes() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let label = jenkins.get_label("master");
+    assert_that!(label).is_ok();
+    let label = label.unwrap();
+    assert_that!(label.name).is_equal_to("master".to_string());
+    assert_that!(label.total_executors).is_equal_to(label.busy_executors + label.idle_executors);
+    assert_that!(label.nodes.len()).is_equal_to(1);
+
+    let nodes = jenkins.get_nodes_matching("master || !master");
+    assert_that!(nodes).is_ok();
+    assert_that!(nodes.unwrap().len()).is_equal_to(jenkins.get_nodes().unwrap().computers.len());
+
+    assert_that!(jenkins.get_nodes_matching("master &&")).is_err();
+}
+
+#[test]
+fn can_get_load_statistics() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let overall = jenkins.get_overall_load();
+    assert_that!(overall).is_ok();
+    let overall = overall.unwrap();
+    assert_that!(overall.total_executors).is_some();
+    assert_that!(overall
+        .to_csv(jenkins_api::nodes::load::Timescale::Sec10)
+        .starts_with("seconds_ago,"))
+    .is_true();
+
+    assert_that!(jenkins.get_node_load_statistics("(master)")).is_ok();
+    assert_that!(
+        jenkins
+            .get_label("master")
+            .unwrap()
+            .load_statistics
+            .busy_executors
+    )
+    .is_some();
+}
+
+#[test]
+fn can_read_node_monitors_and_health() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let master = jenkins.get_master_node().unwrap();
+    let monitors = master.monitors();
+    assert_that!(monitors).is_ok();
+    assert_that!(monitors.unwrap().architecture).is_some();
+
+    let health = jenkins
+        .get_nodes()
+        .unwrap()
+        .health(&jenkins_api::nodes::monitor::HealthThresholds::default());
+    assert_that!(health).is_ok();
+}
+
+#[test]
+fn can_run_groovy_script() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+    assert_that!(jenkins.run_groovy("println(1 + 1)")).is_err();
+
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .enable_script_console()
+        .build()
+        .unwrap();
+    let output = jenkins.run_groovy("println(1 + 1)");
+    assert_that!(output).is_ok();
+    assert_that!(output.unwrap().trim().to_string()).is_equal_to("2".to_string());
+
+    assert_that!(jenkins.run_groovy("throw new IllegalStateException('oops')")).is_err();
+
+    let master = jenkins.get_master_node().unwrap();
+    assert_that!(master.run_groovy(&jenkins, "println('on node')")).is_ok();
+}
+
+#[test]
+fn can_quiet_down_and_cancel() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    assert_that!(jenkins.quiet_down(Some("maintenance"))).is_ok();
+    assert_that!(jenkins.get_home().unwrap().quieting_down).is_true();
+    assert_that!(jenkins.cancel_quiet_down()).is_ok();
+    assert_that!(jenkins.get_home().unwrap().quieting_down).is_false();
+
+    assert_that!(jenkins.wait_until_ready(std::time::Duration::from_secs(5))).is_ok();
+}
+
+#[test]
+fn can_get_server_info() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let info = jenkins.server_info();
+    assert_that!(info).is_ok();
+    let info = info.unwrap();
+    assert_that!(info.version).is_some();
+    assert_that!(info.use_security).is_true();
+
+    assert_that!(jenkins.server_version().unwrap()).is_equal_to(info.version);
+    assert_that!(jenkins.get_master_node()).is_ok();
+}
+
+#[test]
+fn can_list_plugins_and_update_center() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let plugins = jenkins.get_plugins();
+    assert_that!(plugins).is_ok();
+    let plugins = plugins.unwrap();
+    assert_that!(plugins.plugins.len()).is_greater_than(0);
+    assert_that!(plugins.drift(&plugins).len()).is_equal_to(0);
+
+    assert_that!(jenkins.get_update_center()).is_ok();
+}
+
+#[test]
+fn can_manage_credentials() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::credentials::{
+        CredentialBuilder, CredentialKind, StoreLocation, GLOBAL_DOMAIN,
+    };
+
+    let store = jenkins.get_credentials_store(StoreLocation::System);
+    assert_that!(store).is_ok();
+    assert_that!(store.unwrap().domains.contains_key(GLOBAL_DOMAIN)).is_true();
+
+    let credential = CredentialBuilder::new(
+        "rotated-credential",
+        CredentialKind::UsernamePassword {
+            username: "bot",
+            password: "first",
+        },
+    )
+    .with_description("before rotation");
+    assert_that!(jenkins.create_credential(StoreLocation::System, GLOBAL_DOMAIN, &credential))
+        .is_ok();
+
+    let read = jenkins.get_credential(StoreLocation::System, GLOBAL_DOMAIN, "rotated-credential");
+    assert_that!(read).is_ok();
+    assert_that!(read.unwrap().description).is_equal_to(Some("before rotation".to_string()));
+
+    let credential = CredentialBuilder::new(
+        "rotated-credential",
+        CredentialKind::UsernamePassword {
+            username: "bot",
+            password: "second",
+        },
+    )
+    .with_description("after rotation");
+    assert_that!(jenkins.update_credential(StoreLocation::System, GLOBAL_DOMAIN, &credential))
+        .is_ok();
+
+    assert_that!(jenkins.delete_credential(
+        StoreLocation::System,
+        GLOBAL_DOMAIN,
+        "rotated-credential"
+    ))
+    .is_ok();
+    assert_that!(jenkins.get_credential(
+        StoreLocation::System,
+        GLOBAL_DOMAIN,
+        "rotated-credential"
+    ))
+    .is_err();
+}
+
+#[test]
+fn can_get_users() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let me = jenkins.who_am_i();
+    assert_that!(me).is_ok();
+    let me = me.unwrap();
+    assert_that!(me.name).is_equal_to("user".to_string());
+    assert_that!(me.authenticated).is_true();
+
+    let user = jenkins.get_user("user");
+    assert_that!(user).is_ok();
+    assert_that!(user.unwrap().id).is_equal_to("user".to_string());
+
+    assert_that!(jenkins.list_people()).is_ok();
+}
+
+#[test]
+fn can_check_permissions() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::permission::{Permission, PermissionTarget};
+
+    let can_delete =
+        jenkins.has_permission(PermissionTarget::Job("normal job"), Permission::Delete);
+    assert_that!(can_delete).is_ok();
+    assert_that!(can_delete.unwrap()).is_true();
+    assert_that!(jenkins.has_permission(PermissionTarget::Jenkins, Permission::Administer)).is_ok();
+    assert_that!(jenkins.has_permission(PermissionTarget::Jenkins, Permission::Delete)).is_err();
+    assert_that!(jenkins.has_permission(PermissionTarget::Job("missing job"), Permission::Delete))
+        .is_err();
+}
+
+#[test]
+fn can_create_folder() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    assert_that!(jenkins.create_folder(
+        "created team/created project",
+        Some("Created Project"),
+        Some("created from the client")
+    ))
+    .is_ok();
+
+    let config = jenkins.get_job_config_xml("created team/created project");
+    assert_that!(config).is_ok();
+    let config = config.unwrap();
+    assert_that!(config.contains("created from the client")).is_true();
+    assert_that!(jenkins.update_job_config_xml(
+        "created team/created project",
+        &config.replace("created from the client", "updated from the client")
+    ))
+    .is_ok();
+
+    let folder = jenkins.get_job("created team");
+    assert_that!(folder).is_ok();
+    let folder = folder
+        .unwrap()
+        .as_variant::<jenkins_api::job::Folder>()
+        .unwrap();
+    let jobs = folder.jobs();
+    assert_that!(jobs).has_length(1);
+    assert_that!(jobs[0].full_name()).is_equal_to("created team/created project".to_string());
+
+    assert_that!(jenkins.delete_job("created team")).is_ok();
+}
+
+#[test]
+fn can_edit_build() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let build = jenkins.get_build("normal job", 1);
+    assert_that!(build).is_ok();
+    let build = build.unwrap();
+
+    assert_that!(build.set_description(&jenkins, "deployed 1.2.3")).is_ok();
+    assert_that!(build.set_display_name(&jenkins, Some("release 1.2.3"))).is_ok();
+    assert_that!(build.toggle_keep_forever(&jenkins)).is_ok();
+
+    let edited = jenkins.get_build("normal job", 1).unwrap();
+    assert_that!(edited.description).is_equal_to(Some("deployed 1.2.3".to_string()));
+    assert_that!(edited.display_name).is_equal_to("release 1.2.3".to_string());
+    assert_that!(edited.keep_log).is_true();
+
+    assert_that!(build.set_display_name(&jenkins, None)).is_ok();
+    let reset = jenkins.get_build("normal job", 1).unwrap();
+    assert_that!(reset.display_name).is_equal_to("#1".to_string());
+
+    assert_that!(build.toggle_keep_forever(&jenkins)).is_ok();
+}
+
+#[test]
+fn can_patch_job_config() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::job::patch::{BuildDiscarder, JobConfigPatch};
+
+    let job = jenkins.get_job("normal job").unwrap();
+    assert_that!(job.set_description(&jenkins, "edited description")).is_ok();
+    assert_that!(job.set_display_name(&jenkins, Some("Edited Job"))).is_ok();
+    assert_that!(jenkins.patch_job_config(
+        "normal job",
+        &JobConfigPatch::new()
+            .with_concurrent_builds(false)
+            .with_build_discarder(Some(BuildDiscarder {
+                num_to_keep: Some(20),
+                ..Default::default()
+            }))
+    ))
+    .is_ok();
+
+    let edited = jenkins.get_job("normal job").unwrap();
+    assert_that!(edited
+        .get_config_xml(&jenkins)
+        .unwrap()
+        .contains("edited description"))
+    .is_true();
+    assert_that!(edited.display_name).is_equal_to("Edited Job".to_string());
+
+    assert_that!(job.set_display_name(&jenkins, None)).is_ok();
+}
+
+#[test]
+fn can_edit_typed_job_config() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::job::config::JobConfig;
+
+    let config = jenkins.get_job_config("pipeline job");
+    assert_that!(config).is_ok();
+    let mut config = match config.unwrap() {
+        JobConfig::WorkflowJob(config) => config,
+        other => panic!("unexpected config {:?}", other),
+    };
+    let description = config.description.clone();
+    config.description = "typed description".to_string();
+    assert_that!(jenkins.update_job_config("pipeline job", &JobConfig::WorkflowJob(config)))
+        .is_ok();
+
+    let mut config = match jenkins.get_job_config("pipeline job").unwrap() {
+        JobConfig::WorkflowJob(config) => config,
+        other => panic!("unexpected config {:?}", other),
+    };
+    assert_that!(config.description).is_equal_to("typed description".to_string());
+    config.description = description;
+    assert_that!(jenkins.update_job_config("pipeline job", &JobConfig::WorkflowJob(config)))
+        .is_ok();
+}
+
+#[test]
+fn can_detect_drift() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::job::diff::{config_diff, Drift};
+
+    let live = jenkins.get_job_config_xml("normal job").unwrap();
+    assert_that!(config_diff(&live, &live).unwrap()).is_empty();
+
+    let mut expected = std::collections::HashMap::new();
+    expected.insert("normal job".to_string(), live.clone());
+    expected.insert("missing job".to_string(), live);
+    let drifts = jenkins.detect_drift(&expected);
+    assert_that!(drifts).is_ok();
+    assert_that!(drifts.unwrap()).is_equal_to(vec![Drift::Missing {
+        job: "missing job".to_string(),
+    }]);
+}
+
+#[test]
+fn can_export_and_plan_import() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::export::Operation;
+
+    let dir = std::env::temp_dir().join(format!(
+        "jenkins-api-export-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    let exported = jenkins.export_jobs(&dir);
+    assert_that!(exported).is_ok();
+    assert_that!(dir
+        .join("jobs")
+        .join("normal job")
+        .join("config.xml")
+        .is_file())
+    .is_true();
+
+    let plan = jenkins.plan_import(&dir);
+    assert_that!(plan).is_ok();
+    let plan = plan.unwrap();
+    assert_that!(plan.actions.len()).is_equal_to(exported.unwrap().len());
+    assert_that!(plan
+        .actions
+        .iter()
+        .all(|action| action.operation == Operation::Update))
+    .is_true();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn can_dry_run_sync() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    use jenkins_api::sync::{sync, SyncMode};
+
+    let report = sync(&jenkins, &jenkins, |_| true, SyncMode::DryRun);
+    assert_that!(report).is_ok();
+    let report = report.unwrap();
+    assert_that!(report.actions).is_empty();
+    assert_that!(report.missing_plugins).is_empty();
+}
+
+#[test]
+fn can_validate_jenkinsfile() {
+    setup();
+    let jenkins = JenkinsBuilder::new(JENKINS_URL)
+        .with_user("user", Some("password"))
+        .build()
+        .unwrap();
+
+    let jenkinsfile = "pipeline {\n  agent any\n  stages {\n    stage('Build') {\n      steps {\n        echo 'building'\n      }\n    }\n  }\n}\n";
+    let errors = jenkins.validate_jenkinsfile(jenkinsfile);
+    assert_that!(errors).is_ok();
+    assert_that!(errors.unwrap()).is_empty();
+
+    let errors = jenkins.validate_jenkinsfile("pipeline {\n  agent any\n  stages {\n    stage('Build') {\n      echo 'building'\n    }\n  }\n}\n");
+    assert_that!(errors).is_ok();
+    let errors = errors.unwrap();
+    assert_that!(errors.first().and_then(|error| error.line)).is_some();
+
+    let json = jenkins.jenkinsfile_to_json(jenkinsfile);
+    assert_that!(json).is_ok();
+    let converted = jenkins.json_to_jenkinsfile(&json.unwrap());
+    assert_that!(converted).is_ok();
+    assert_that!(jenkins.validate_jenkinsfile(&converted.unwrap()).unwrap()).is_empty();
+}